@@ -9,6 +9,7 @@ use mywheel_rs::bpqueue::BPQueue;
 use mywheel_rs::dllist::{Dllink, Dllist};
 use mywheel_rs::map_adapter::MapAdapter;
 use mywheel_rs::robin::Robin;
+use mywheel_rs::sort::bucket_sort_by_key;
 use std::collections::{BinaryHeap, LinkedList};
 
 /// Benchmark RepeatArray vs Vec for constant data access
@@ -163,6 +164,43 @@ fn bench_robin_iteration(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark bucket_sort_by_key vs slice::sort_by_key on bounded keys
+fn bench_bucket_sort(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bucket_sort");
+
+    for size in [100, 1000, 10000].iter() {
+        let data: Vec<(i32, usize)> = (0..*size)
+            .map(|i| (((i * 7919) % 201) as i32 - 100, i))
+            .collect();
+
+        group.bench_with_input(
+            BenchmarkId::new("mywheel_bucket_sort", size),
+            &data,
+            |b, data| {
+                b.iter(|| {
+                    let mut v = data.clone();
+                    bucket_sort_by_key(&mut v, |x| x.0, -100..=100);
+                    v
+                });
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("std_sort_by_key", size),
+            &data,
+            |b, data| {
+                b.iter(|| {
+                    let mut v = data.clone();
+                    v.sort_by_key(|x| x.0);
+                    v
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_repeat_array_access,
@@ -170,6 +208,7 @@ criterion_group!(
     bench_dllist_append,
     bench_bpqueue_insert,
     bench_map_adapter_access,
-    bench_robin_iteration
+    bench_robin_iteration,
+    bench_bucket_sort
 );
 criterion_main!(benches);
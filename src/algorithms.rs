@@ -0,0 +1,2 @@
+/// Greedy set cover accelerated by BPQueue gains
+pub mod set_cover;
@@ -0,0 +1,267 @@
+use crate::bpqueue::{BPQueue, InsertOrder};
+use crate::dllist::Dllink;
+
+/// Greedy unweighted set cover.
+///
+/// Repeatedly picks the set that covers the most uncovered elements. The
+/// uncovered counts only ever drop by one, so they are kept in a
+/// [`BPQueue`] and every update is an O(1) `decrease_key`. The queue nodes
+/// are owned by this function; callers never see raw pointers.
+///
+/// # Performance Characteristics
+///
+/// * **Time Complexity**: O(Σ|S| + K) where K is the largest set size
+/// * **Space Complexity**: O(Σ|S| + K)
+/// * **Approximation**: Within a factor H(K) ≤ 1 + ln K of the optimum
+///
+/// Arguments:
+///
+/// * `sets`: The candidate sets, each given as a list of element indices.
+///   Duplicate indices within a set are ignored.
+/// * `num_elements`: The size of the universe `0..num_elements`.
+///
+/// Returns:
+///
+/// The indices of the chosen sets in the order they were picked, or `None`
+/// if some element is not contained in any set.
+///
+/// # Panics
+///
+/// Panics if a set contains an element outside `0..num_elements`.
+///
+/// # Examples
+///
+/// ```rust
+/// use mywheel_rs::algorithms::set_cover::greedy_set_cover;
+///
+/// let sets = [vec![0, 1, 2], vec![2, 3], vec![3, 4], vec![0, 3]];
+///
+/// assert_eq!(greedy_set_cover(&sets, 5), Some(vec![0, 2]));
+/// assert_eq!(greedy_set_cover(&sets, 6), None);
+/// ```
+pub fn greedy_set_cover<S: AsRef<[usize]>>(sets: &[S], num_elements: usize) -> Option<Vec<usize>> {
+    greedy(sets, &vec![1; sets.len()], num_elements)
+}
+
+/// Greedy weighted set cover with integer costs.
+///
+/// Repeatedly picks the set with the most uncovered elements per unit of
+/// cost. The ratio is kept as the integer key
+/// `uncovered * max_cost / cost` in a [`BPQueue`], so sets whose ratios
+/// differ by less than `1 / max_cost` are treated as ties. With unit costs
+/// this is exactly [`greedy_set_cover`].
+///
+/// # Performance Characteristics
+///
+/// * **Time Complexity**: O(Σ|S| + K·C) where C is the largest cost
+/// * **Space Complexity**: O(Σ|S| + K·C)
+///
+/// Arguments:
+///
+/// * `sets`: The candidate sets, each given as a list of element indices.
+/// * `costs`: The positive cost of every set, parallel to `sets`.
+/// * `num_elements`: The size of the universe `0..num_elements`.
+///
+/// Returns:
+///
+/// The indices of the chosen sets in the order they were picked, or `None`
+/// if some element is not contained in any set.
+///
+/// # Panics
+///
+/// Panics if `costs` does not match `sets`, if a cost is zero, if a set
+/// contains an element outside `0..num_elements`, or if the key range
+/// does not fit in an `i32`.
+///
+/// # Examples
+///
+/// ```rust
+/// use mywheel_rs::algorithms::set_cover::greedy_weighted_set_cover;
+///
+/// let sets = [vec![0, 1, 2, 3], vec![0, 1], vec![2, 3]];
+///
+/// assert_eq!(greedy_weighted_set_cover(&sets, &[5, 1, 1], 4), Some(vec![1, 2]));
+/// assert_eq!(greedy_weighted_set_cover(&sets, &[3, 2, 2], 4), Some(vec![0]));
+/// ```
+pub fn greedy_weighted_set_cover<S: AsRef<[usize]>>(
+    sets: &[S],
+    costs: &[u32],
+    num_elements: usize,
+) -> Option<Vec<usize>> {
+    assert_eq!(costs.len(), sets.len());
+    assert!(costs.iter().all(|&c| c > 0), "set costs must be positive");
+    greedy(sets, costs, num_elements)
+}
+
+fn greedy<S: AsRef<[usize]>>(sets: &[S], costs: &[u32], num_elements: usize) -> Option<Vec<usize>> {
+    let members: Vec<Vec<usize>> = sets
+        .iter()
+        .map(|s| {
+            let mut m = s.as_ref().to_vec();
+            m.sort_unstable();
+            m.dedup();
+            assert!(
+                m.last().map_or(true, |&e| e < num_elements),
+                "element out of range"
+            );
+            m
+        })
+        .collect();
+    let mut owners = vec![vec![]; num_elements];
+    for (i, m) in members.iter().enumerate() {
+        for &e in m {
+            owners[e].push(i);
+        }
+    }
+    if owners.iter().any(|o| o.is_empty()) {
+        return None;
+    }
+
+    // A set with at least one uncovered element always has a key >= 1.
+    let max_cost = costs.iter().copied().max().unwrap_or(1) as usize;
+    let key = |uncovered: usize, i: usize| uncovered * max_cost / costs[i] as usize;
+    let mut uncovered: Vec<usize> = members.iter().map(|m| m.len()).collect();
+    let max_key = (0..sets.len())
+        .map(|i| key(uncovered[i], i))
+        .max()
+        .unwrap_or(0);
+    let max_key = i32::try_from(max_key.max(1)).expect("set cover key range exceeds i32");
+
+    let mut bpq = BPQueue::<usize>::new(1, max_key);
+    let mut nodes: Vec<Dllink<(usize, usize)>> =
+        (0..sets.len()).map(|i| Dllink::new((0, i))).collect();
+    // A locked node is either picked or has nothing left to cover.
+    for node in nodes.iter_mut() {
+        node.lock();
+    }
+    bpq.extend_from_ids(
+        &mut nodes,
+        (0..sets.len())
+            .filter(|&i| uncovered[i] > 0)
+            .map(|i| (i, key(uncovered[i], i) as i32)),
+        InsertOrder::Stable,
+    );
+
+    let mut covered = vec![false; num_elements];
+    let mut cover = vec![];
+    while !bpq.is_empty() {
+        // Safety: the queue is not empty, so popleft returns one of `nodes`
+        let s = unsafe { (*bpq.popleft()).data.1 };
+        nodes[s].lock();
+        cover.push(s);
+        for &e in &members[s] {
+            if covered[e] {
+                continue;
+            }
+            covered[e] = true;
+            for &t in &owners[e] {
+                if nodes[t].is_locked() {
+                    continue;
+                }
+                let old = key(uncovered[t], t);
+                uncovered[t] -= 1;
+                let new = key(uncovered[t], t);
+                if uncovered[t] == 0 {
+                    bpq.detach(&mut nodes[t]);
+                    nodes[t].lock();
+                } else if new < old {
+                    bpq.decrease_key(&mut nodes[t], old - new);
+                }
+            }
+        }
+    }
+    Some(cover)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    #[test]
+    fn test_set_cover_unit() {
+        let sets = [
+            vec![0, 1, 2, 3],
+            vec![4, 5, 6],
+            vec![0, 4],
+            vec![3, 6],
+            vec![7],
+        ];
+        assert_eq!(greedy_set_cover(&sets, 8), Some(vec![0, 1, 4]));
+        assert_eq!(greedy_set_cover(&[Vec::<usize>::new()], 0), Some(vec![]));
+        let empty: [Vec<usize>; 0] = [];
+        assert_eq!(greedy_set_cover(&empty, 1), None);
+    }
+
+    #[test]
+    fn test_set_cover_duplicates() {
+        // Set 1 lists element 2 three times but only covers two elements.
+        let sets = [vec![0, 1], vec![2, 2, 2, 3], vec![1, 2, 3]];
+        assert_eq!(greedy_set_cover(&sets, 4), Some(vec![2, 0]));
+    }
+
+    #[test]
+    fn test_set_cover_weighted() {
+        let sets = [
+            vec![0, 1, 2, 3, 4, 5],
+            vec![0, 1, 2],
+            vec![3, 4, 5],
+            vec![5],
+        ];
+        // Sets 2 and 3 tie at one element per unit cost; set 2 came first.
+        assert_eq!(
+            greedy_weighted_set_cover(&sets, &[10, 2, 3, 1], 6),
+            Some(vec![1, 2])
+        );
+        assert_eq!(
+            greedy_weighted_set_cover(&sets, &[10, 2, 4, 1], 6),
+            Some(vec![1, 3, 2])
+        );
+        assert_eq!(
+            greedy_weighted_set_cover(&sets, &[1, 1, 1, 1], 6),
+            Some(vec![0])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_set_cover_zero_cost() {
+        greedy_weighted_set_cover(&[vec![0]], &[0], 1);
+    }
+
+    #[test]
+    fn prop_set_cover_valid() {
+        fn prop(sets: Vec<Vec<u8>>, costs: Vec<u8>) -> bool {
+            let n = 20;
+            let sets: Vec<Vec<usize>> = sets
+                .iter()
+                .map(|s| s.iter().map(|&e| e as usize % n).collect())
+                .collect();
+            let costs: Vec<u32> = (0..sets.len())
+                .map(|i| costs.get(i).map_or(1, |&c| c as u32 % 7 + 1))
+                .collect();
+            let coverable = (0..n).all(|e| sets.iter().any(|s| s.contains(&e)));
+            [
+                greedy_set_cover(&sets, n),
+                greedy_weighted_set_cover(&sets, &costs, n),
+            ]
+            .iter()
+            .all(|cover| match cover {
+                None => !coverable,
+                Some(cover) => {
+                    // Every pick covers something new and together they cover all.
+                    let mut covered = vec![false; n];
+                    let gains = cover.iter().all(|&s| {
+                        let gain = sets[s].iter().filter(|&&e| !covered[e]).count();
+                        for &e in &sets[s] {
+                            covered[e] = true;
+                        }
+                        gain > 0
+                    });
+                    coverable && gains && covered.iter().all(|&c| c)
+                }
+            })
+        }
+        QuickCheck::new().quickcheck(prop as fn(Vec<Vec<u8>>, Vec<u8>) -> bool);
+    }
+}
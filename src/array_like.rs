@@ -1,3 +1,7 @@
+use crate::map_adapter::MapAdapter;
+use std::marker::PhantomData;
+use std::ops::{Bound, Range, RangeBounds};
+
 /// The `RepeatArray` struct represents an array that contains a single value repeated a specified
 /// number of times.
 ///
@@ -158,6 +162,45 @@ impl<T: Copy> std::ops::Index<usize> for RepeatArray<T> {
     }
 }
 
+/// Index types usable as the `start` of a [`ShiftArray`].
+///
+/// Implemented for `usize` and for the signed types `isize`, `i32` and `i64`,
+/// so that tables keyed by, e.g., gains in `-pmax..=pmax` can be indexed
+/// directly by the gain.
+pub trait ShiftIndex: Copy + PartialOrd + std::fmt::Debug {
+    /// Returns the position of `self` counted from `start`, or `None` if
+    /// `self` lies before `start` or the distance does not fit in `usize`.
+    fn offset_from(self, start: Self) -> Option<usize>;
+
+    /// Returns the index `n` places after `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result does not fit in `Self`.
+    fn add_usize(self, n: usize) -> Self;
+}
+
+macro_rules! impl_shift_index {
+    ($($t:ty),*) => {$(
+        impl ShiftIndex for $t {
+            #[inline]
+            fn offset_from(self, start: Self) -> Option<usize> {
+                usize::try_from(self.checked_sub(start)?).ok()
+            }
+
+            #[inline]
+            fn add_usize(self, n: usize) -> Self {
+                <$t>::try_from(n)
+                    .ok()
+                    .and_then(|n| self.checked_add(n))
+                    .expect("shifted index overflow")
+            }
+        }
+    )*};
+}
+
+impl_shift_index!(usize, isize, i32, i64);
+
 /// The `ShiftArray` type represents an array that can be shifted to the left or right without copying or
 /// moving its elements.
 ///
@@ -165,7 +208,9 @@ impl<T: Copy> std::ops::Index<usize> for RepeatArray<T> {
 ///
 /// * **Time Complexity**: O(1) for access, O(n) for iteration
 /// * **Space Complexity**: O(n) where n is number of elements
-/// * **Memory Overhead**: Minimal - start offset + Vec pointer
+/// * **Memory Overhead**: Minimal - start and head offsets + Vec pointer, plus
+///   any hidden front capacity left by [`ShiftArray::set_or_grow`] and
+///   [`ShiftArray::trim_front`]
 /// * **Cache Performance**: Good - preserves locality, allows in-place operations
 /// * **Use Cases**: Sliding windows, circular buffers, offset-based addressing
 /// * **vs `Vec<T>`**: Same memory, additional offset calculation overhead
@@ -179,13 +224,18 @@ impl<T: Copy> std::ops::Index<usize> for RepeatArray<T> {
 /// Properties:
 ///
 /// * `start`: The `start` property represents the index of the first element in the `ShiftArray`. It
-///   indicates the starting point from which elements are accessed or shifted.
-/// * `lst`: The `lst` property is a vector that holds the elements of the `ShiftArray`. It is of type
-///   `Vec<T>`, where `T` is a generic type parameter that can be replaced with any type.
-#[derive(Clone, PartialEq, Eq)]
-pub struct ShiftArray<T> {
-    pub start: usize,
-    pub lst: Vec<T>,
+///   indicates the starting point from which elements are accessed or shifted. Its type `S` is any
+///   [`ShiftIndex`] and defaults to `usize`; a signed `S` allows negative indices.
+///
+/// The elements themselves are private: the backing vector may hold hidden
+/// capacity before the first element, so read them through
+/// [`ShiftArray::as_slice`] or [`ShiftArray::into_vec`].
+#[derive(Clone)]
+pub struct ShiftArray<T, S = usize> {
+    pub start: S,
+    /// Backing storage; `buf[..head]` is hidden front capacity.
+    buf: Vec<T>,
+    head: usize,
 }
 
 impl<T> ShiftArray<T> {
@@ -207,21 +257,89 @@ impl<T> ShiftArray<T> {
     ///
     /// let mut shift_array = ShiftArray::new(vec![1, 2, 3]);
     /// assert_eq!(shift_array.start, 0);
-    /// assert_eq!(shift_array.lst, vec![1, 2, 3]);
+    /// assert_eq!(shift_array.as_slice(), &[1, 2, 3]);
     /// assert_eq!(shift_array.len(), 3);
     /// assert_eq!(shift_array[0], 1);
     /// ```
     pub fn new(lst: Vec<T>) -> Self {
-        Self { start: 0, lst }
+        Self {
+            start: 0,
+            buf: lst,
+            head: 0,
+        }
+    }
+}
+
+impl<T, S> ShiftArray<T, S> {
+    /// Returns the elements as a slice, the first one at `start`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mywheel_rs::array_like::ShiftArray;
+    ///
+    /// let shift_array = ShiftArray::with_start(vec![1, 2, 3], 4);
+    /// assert_eq!(shift_array.as_slice(), &[1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.buf[self.head..]
+    }
+
+    /// Returns the elements as a mutable slice, the first one at `start`.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.buf[self.head..]
+    }
+
+    /// Consumes the array and returns its elements, dropping any hidden
+    /// front capacity.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mywheel_rs::array_like::ShiftArray;
+    ///
+    /// let mut window = ShiftArray::with_start(vec![1, 2, 3], 4);
+    /// window.trim_front(1);
+    /// assert_eq!(window.into_vec(), vec![2, 3]);
+    /// ```
+    pub fn into_vec(mut self) -> Vec<T> {
+        self.buf.drain(..self.head);
+        self.buf
+    }
+}
+
+impl<T, S: ShiftIndex> ShiftArray<T, S> {
+    /// Creates a new `ShiftArray` whose first element has index `start`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use mywheel_rs::array_like::ShiftArray;
+    ///
+    /// // A gain table over -2..=2
+    /// let mut gains = ShiftArray::with_start(vec![0; 5], -2);
+    /// gains[-2] = 7;
+    /// gains[2] = 9;
+    /// assert_eq!(gains.as_slice(), &[7, 0, 0, 0, 9]);
+    /// assert_eq!(gains.get(-3), None);
+    /// assert_eq!(gains.get(3), None);
+    /// ```
+    pub fn with_start(lst: Vec<T>, start: S) -> Self {
+        Self {
+            start,
+            buf: lst,
+            head: 0,
+        }
     }
 
     /// The function sets the start value of a variable.
     ///
     /// Arguments:
     ///
-    /// * `start`: The `start` parameter is of type `usize`, which represents an unsigned integer that
-    ///   can hold the size of any object in memory. It is used to set the value of the `start` field in
-    ///   the struct or object that this method belongs to.
+    /// * `start`: The `start` parameter is the new index of the first element. It is used to set the
+    ///   value of the `start` field in the struct or object that this method belongs to.
     ///
     /// # Examples
     ///
@@ -233,12 +351,12 @@ impl<T> ShiftArray<T> {
     /// assert_eq!(shift_array.start, 1);
     /// assert_eq!(shift_array.len(), 3);
     /// ```
-    pub fn set_start(&mut self, start: usize) {
+    pub fn set_start(&mut self, start: S) {
         self.start = start;
     }
 
     /// The `items` function returns an iterator that yields the index and reference to each element in
-    /// the `lst` vector, with the index adjusted by the `start` value.
+    /// the array, with the index adjusted by the `start` value.
     ///
     /// # Examples
     ///
@@ -250,22 +368,286 @@ impl<T> ShiftArray<T> {
     ///     assert_eq!(i, *v as usize);
     /// }
     /// ```
-    pub fn items(&self) -> impl Iterator<Item = (usize, &T)> {
-        self.lst
+    pub fn items(&self) -> impl Iterator<Item = (S, &T)> {
+        self.as_slice()
             .iter()
             .enumerate()
-            .map(move |(i, v)| (i + self.start, v))
+            .map(move |(i, v)| (self.start.add_usize(i), v))
     }
 
     /// Returns an iterator over the elements.
     ///
     /// # Returns
     ///
-    /// A `ShiftArrayIterator` that yields owned values.
-    pub fn iter(&self) -> ShiftArrayIterator<'_, T> {
+    /// A `ShiftArrayIterator` that yields owned values, in the same order as
+    /// [`ShiftArray::items`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::ShiftArray;
+    /// let mut shift_array = ShiftArray::new(vec![1, 2, 3]);
+    /// shift_array.set_start(5);
+    /// assert_eq!(shift_array.iter().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// ```
+    pub fn iter(&self) -> ShiftArrayIterator<'_, T, S> {
         ShiftArrayIterator {
             array: self,
-            current: self.start,
+            current: 0,
+        }
+    }
+
+    /// Returns a mutable iterator over the elements.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::ShiftArray;
+    /// let mut shift_array = ShiftArray::new(vec![1, 2, 3]);
+    /// shift_array.set_start(5);
+    /// for v in shift_array.iter_mut() {
+    ///     *v *= 10;
+    /// }
+    /// assert_eq!(shift_array[7], 30);
+    /// ```
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.as_mut_slice().iter_mut()
+    }
+
+    /// Get a reference to the element at the shifted index `key`, if it exists.
+    ///
+    /// Unlike indexing, this returns `None` instead of panicking when `key`
+    /// is below `start` or past the end. Use [`ArrayLike::get_pos`] for
+    /// positional access that ignores `start`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::ShiftArray;
+    /// let mut shift_array = ShiftArray::new(vec![1, 2, 3]);
+    /// shift_array.set_start(5);
+    /// assert_eq!(shift_array.get(4), None);
+    /// assert_eq!(shift_array.get(5), Some(&1));
+    /// assert_eq!(shift_array.get(8), None);
+    /// ```
+    #[inline]
+    pub fn get(&self, key: S) -> Option<&T> {
+        key.offset_from(self.start)
+            .and_then(|i| self.as_slice().get(i))
+    }
+
+    /// Get a mutable reference to the element at the shifted index `key`, if it exists.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::ShiftArray;
+    /// let mut shift_array = ShiftArray::new(vec![1, 2, 3]);
+    /// shift_array.set_start(5);
+    /// if let Some(v) = shift_array.get_mut(6) {
+    ///     *v = 20;
+    /// }
+    /// assert_eq!(shift_array[6], 20);
+    /// assert!(shift_array.get_mut(0).is_none());
+    /// ```
+    #[inline]
+    pub fn get_mut(&mut self, key: S) -> Option<&mut T> {
+        key.offset_from(self.start)
+            .and_then(move |i| self.as_mut_slice().get_mut(i))
+    }
+
+    /// Append an element at shifted index `start + len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::ShiftArray;
+    /// let mut shift_array = ShiftArray::new(vec![1]);
+    /// shift_array.set_start(5);
+    /// shift_array.push(2);
+    /// assert_eq!(shift_array[6], 2);
+    /// assert_eq!(shift_array.pop(), Some(2));
+    /// assert_eq!(shift_array.len(), 1);
+    /// ```
+    #[inline]
+    pub fn push(&mut self, value: T) {
+        self.buf.push(value);
+    }
+
+    /// Remove and return the last element, if any.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            self.buf.pop()
+        }
+    }
+
+    /// Returns the elements at the shifted indices in `range` as a slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range starts below `start` or ends past the last element.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::ShiftArray;
+    /// let mut shift_array = ShiftArray::new((0..10).collect::<Vec<i32>>());
+    /// shift_array.set_start(3);
+    /// assert_eq!(shift_array.slice(5..8), &[2, 3, 4]);
+    /// assert_eq!(shift_array.slice(..5), &[0, 1]);
+    /// assert_eq!(shift_array.slice(11..=12), &[8, 9]);
+    /// ```
+    pub fn slice<R: RangeBounds<S>>(&self, range: R) -> &[T] {
+        let (lo, hi) = self.positions(range);
+        &self.as_slice()[lo..hi]
+    }
+
+    /// Returns the elements at the shifted indices in `range` as a mutable slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range starts below `start` or ends past the last element.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::ShiftArray;
+    /// let mut shift_array = ShiftArray::new(vec![0; 5]);
+    /// shift_array.set_start(10);
+    /// shift_array.slice_mut(11..13).fill(7);
+    /// assert_eq!(shift_array.as_slice(), &[0, 7, 7, 0, 0]);
+    /// ```
+    pub fn slice_mut<R: RangeBounds<S>>(&mut self, range: R) -> &mut [T] {
+        let (lo, hi) = self.positions(range);
+        &mut self.as_mut_slice()[lo..hi]
+    }
+
+    /// Write `value` at the shifted index `index`, growing the array at either
+    /// end if `index` lies outside it.
+    ///
+    /// Slots opened up between the old elements and `index` are filled with
+    /// clones of `fill`; afterwards the array spans exactly from the lower of
+    /// `start` and `index` to the higher of its old end and `index`. Writes
+    /// below `start` reuse hidden front capacity when there is enough, and
+    /// otherwise reallocate with hidden front capacity of at least the
+    /// current length, so a run of writes that walks `start` downwards is
+    /// amortized O(1) per write. Growth at the back relies on `Vec`'s
+    /// amortized push.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the distance from `index` to `start` does not fit in `usize`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::ShiftArray;
+    /// let mut window = ShiftArray::with_start(vec![1, 2], 10);
+    /// window.set_or_grow(13, 4, 0);
+    /// assert_eq!(window.as_slice(), &[1, 2, 0, 4]);
+    /// window.set_or_grow(8, 8, 0);
+    /// assert_eq!(window.start, 8);
+    /// assert_eq!(window.as_slice(), &[8, 0, 1, 2, 0, 4]);
+    /// ```
+    pub fn set_or_grow(&mut self, index: S, value: T, fill: T)
+    where
+        T: Clone,
+    {
+        if let Some(pos) = index.offset_from(self.start) {
+            if pos < self.len() {
+                self.as_mut_slice()[pos] = value;
+            } else {
+                self.buf.resize(self.head + pos, fill);
+                self.buf.push(value);
+            }
+            return;
+        }
+        let need = self
+            .start
+            .offset_from(index)
+            .expect("shifted index distance overflow");
+        if need <= self.head {
+            self.head -= need;
+            self.buf[self.head + 1..self.head + need].fill(fill);
+        } else {
+            let slack = need.max(self.len());
+            let mut buf = Vec::with_capacity(slack + need + self.len());
+            buf.resize(slack + need, fill);
+            buf.extend(self.buf.drain(self.head..));
+            self.buf = buf;
+            self.head = slack;
+        }
+        self.buf[self.head] = value;
+        self.start = index;
+    }
+
+    /// Drop the first `n` elements and advance `start` past them.
+    ///
+    /// The remaining elements keep their shifted indices. Takes amortized
+    /// O(n) time: the trimmed slots become hidden front capacity, and the
+    /// backing vector is compacted (dropping them) only once that capacity
+    /// exceeds twice the remaining length.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is greater than `len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::ShiftArray;
+    /// let mut window = ShiftArray::with_start(vec![1, 2, 3, 4], 10);
+    /// window.trim_front(3);
+    /// assert_eq!(window.start, 13);
+    /// assert_eq!(window[13], 4);
+    /// assert_eq!(window.get(12), None);
+    /// ```
+    pub fn trim_front(&mut self, n: usize) {
+        assert!(n <= self.len());
+        self.start = self.start.add_usize(n);
+        self.head += n;
+        if self.head > 2 * self.len() {
+            self.buf.drain(..self.head);
+            self.head = 0;
+        }
+    }
+
+    /// Translate a range of shifted indices into a range of positions in `as_slice()`.
+    fn positions<R: RangeBounds<S>>(&self, range: R) -> (usize, usize) {
+        let lo = match range.start_bound() {
+            Bound::Included(&a) => a,
+            Bound::Excluded(&a) => a.add_usize(1),
+            Bound::Unbounded => self.start,
+        };
+        let hi = match range.end_bound() {
+            Bound::Included(&b) => b.add_usize(1),
+            Bound::Excluded(&b) => b,
+            Bound::Unbounded => self.start.add_usize(self.len()),
+        };
+        let lo = match lo.offset_from(self.start) {
+            Some(lo) => lo,
+            None => panic!(
+                "range start {lo:?} is below the array start {:?}",
+                self.start
+            ),
+        };
+        let hi = match hi.offset_from(self.start) {
+            Some(hi) => hi.max(lo),
+            None if hi < self.start => lo,
+            None => usize::MAX,
+        };
+        (lo, hi)
+    }
+
+    /// Translate a shifted index into a position in `as_slice()`.
+    #[inline]
+    fn position(&self, key: S) -> usize {
+        match key.offset_from(self.start) {
+            Some(i) => i,
+            None => panic!("index {key:?} is below the array start {:?}", self.start),
         }
     }
 
@@ -273,18 +655,18 @@ impl<T> ShiftArray<T> {
     ///
     /// # Returns
     ///
-    /// The length of the underlying vector.
+    /// The number of elements, not counting hidden front capacity.
     pub fn len(&self) -> usize {
-        self.lst.len()
+        self.buf.len() - self.head
     }
 
     /// Returns `true` if the array contains no elements.
     ///
     /// # Returns
     ///
-    /// `true` if the underlying vector is empty, `false` otherwise.
+    /// `true` if the array has no elements, `false` otherwise.
     pub fn is_empty(&self) -> bool {
-        self.lst.is_empty()
+        self.len() == 0
     }
 }
 
@@ -294,17 +676,17 @@ impl<T> ShiftArray<T> {
 ///
 /// * `array`: Reference to the array being iterated.
 /// * `current`: The current position in the array.
-pub struct ShiftArrayIterator<'a, T> {
-    array: &'a ShiftArray<T>,
+pub struct ShiftArrayIterator<'a, T, S = usize> {
+    array: &'a ShiftArray<T, S>,
     current: usize,
 }
 
-impl<'a, T: Clone> Iterator for ShiftArrayIterator<'a, T> {
+impl<'a, T: Clone, S> Iterator for ShiftArrayIterator<'a, T, S> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current < self.array.lst.len() {
-            let value = self.array.lst[self.current].clone();
+        if self.current < self.array.len() {
+            let value = self.array.as_slice()[self.current].clone();
             self.current += 1;
             Some(value)
         } else {
@@ -313,20 +695,19 @@ impl<'a, T: Clone> Iterator for ShiftArrayIterator<'a, T> {
     }
 }
 
-impl<T> std::ops::Index<usize> for ShiftArray<T> {
+impl<T, S: ShiftIndex> std::ops::Index<S> for ShiftArray<T, S> {
     type Output = T;
 
     /// The `index` function returns a reference to an element in a list based on a given key.
     ///
     /// Arguments:
     ///
-    /// * `key`: The `key` parameter is of type `usize` and represents the index of the element to be
-    ///   accessed in the `lst` field.
+    /// * `key`: The `key` parameter is of type `S` and represents the index of the element to be
+    ///   accessed.
     ///
     /// Returns:
     ///
-    /// The method `index` returns a reference to an element of `self.lst` at the specified index `key -
-    /// self.start`.
+    /// The method `index` returns a reference to the element at position `key - self.start`.
     ///
     /// # Examples
     ///
@@ -337,23 +718,23 @@ impl<T> std::ops::Index<usize> for ShiftArray<T> {
     /// shift_array.set_start(1);
     /// assert_eq!(shift_array[2], 2);
     /// ```
-    fn index(&self, key: usize) -> &Self::Output {
-        &self.lst[key - self.start]
+    fn index(&self, key: S) -> &Self::Output {
+        &self.as_slice()[self.position(key)]
     }
 }
 
-impl<T> std::ops::IndexMut<usize> for ShiftArray<T> {
+impl<T, S: ShiftIndex> std::ops::IndexMut<S> for ShiftArray<T, S> {
     /// The function `index_mut` returns a mutable reference to an element in a list based on a given
     /// key.
     ///
     /// Arguments:
     ///
-    /// * `key`: The `key` parameter is of type `usize` and represents the index of the element to be
-    ///   accessed in the `lst` vector.
+    /// * `key`: The `key` parameter is of type `S` and represents the index of the element to be
+    ///   accessed.
     ///
     /// Returns:
     ///
-    /// A mutable reference to an element in the `lst` vector, located at the index `key - self.start`.
+    /// A mutable reference to the element at position `key - self.start`.
     ///
     /// # Examples
     ///
@@ -366,107 +747,1155 @@ impl<T> std::ops::IndexMut<usize> for ShiftArray<T> {
     /// shift_array[2] = 4;
     /// assert_eq!(shift_array[2], 4);
     /// ```
-    fn index_mut(&mut self, key: usize) -> &mut Self::Output {
-        &mut self.lst[key - self.start]
+    fn index_mut(&mut self, key: S) -> &mut Self::Output {
+        let i = self.position(key);
+        &mut self.as_mut_slice()[i]
     }
 }
 
-/// Display implementation for RepeatArray.
-///
-/// Formats as: `RepeatArray[size] with value: {value}`
-impl<T: std::fmt::Display> std::fmt::Display for RepeatArray<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "RepeatArray[{}] with value: {}", self.size, self.value)
+impl<'a, T, S> IntoIterator for &'a ShiftArray<T, S> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
     }
 }
 
-/// Debug implementation for RepeatArray.
-impl<T: std::fmt::Debug> std::fmt::Debug for RepeatArray<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("RepeatArray")
-            .field("value", &self.value)
-            .field("size", &self.size)
-            .finish()
+impl<'a, T, S> IntoIterator for &'a mut ShiftArray<T, S> {
+    type Item = &'a mut T;
+    type IntoIter = std::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_mut_slice().iter_mut()
     }
 }
 
-/// Debug implementation for ShiftArray.
-impl<T: std::fmt::Debug> std::fmt::Debug for ShiftArray<T> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("ShiftArray")
-            .field("start", &self.start)
-            .field("len", &self.lst.len())
-            .finish()
+impl<T, S> IntoIterator for ShiftArray<T, S> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_vec().into_iter()
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+impl<T, S> Extend<T> for ShiftArray<T, S> {
+    /// Append the elements of `iter` after the last element.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::ShiftArray;
+    /// let mut shift_array = ShiftArray::new(vec![1]);
+    /// shift_array.set_start(2);
+    /// shift_array.extend(vec![2, 3]);
+    /// assert_eq!(shift_array[4], 3);
+    /// ```
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.buf.extend(iter);
+    }
+}
 
-    #[test]
-    fn test_repeat_array() {
-        let arr: RepeatArray<i32> = RepeatArray::new(1, 10);
-        assert_eq!(arr.len(), 10);
-        assert_eq!(arr[4], 1);
-        for i in arr.iter() {
-            assert_eq!(i, 1);
+/// The `RingArray` type is a fixed-capacity circular buffer addressed by
+/// monotonically increasing logical indices.
+///
+/// The `k`-th value ever pushed has logical index `k` and lives in slot
+/// `k % capacity`. Once more than `capacity` values have been pushed, each
+/// push overwrites the oldest one, whose index then reads as `None`.
+///
+/// ```svgbob
+///   logical:   4   5   6   7      (start = 4, end = 8)
+///             +---+---+---+---+
+///   slots:    | 4 | 5 | 6 | 7 |   capacity = 4
+///             +---+---+---+---+
+///               0   1   2   3
+/// ```
+///
+/// # Performance Characteristics
+///
+/// * **Time Complexity**: O(1) for `push_back` and access, O(n) for iteration
+/// * **Space Complexity**: O(capacity)
+/// * **Memory Overhead**: Minimal - capacity and push counter + Vec pointer
+/// * **Use Cases**: Sliding windows over time steps, recent-history logs
+///
+/// Properties:
+///
+/// * `buf`: The slots, holding at most `capacity` values.
+/// * `capacity`: The number of values kept.
+/// * `end`: The logical index the next pushed value will get.
+#[derive(Clone)]
+pub struct RingArray<T> {
+    buf: Vec<T>,
+    capacity: usize,
+    end: usize,
+}
+
+impl<T> RingArray<T> {
+    /// Creates an empty `RingArray` that keeps the last `capacity` values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::RingArray;
+    /// let ring: RingArray<i32> = RingArray::new(3);
+    /// assert_eq!(ring.capacity(), 3);
+    /// assert!(ring.is_empty());
+    /// ```
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0);
+        Self {
+            buf: Vec::with_capacity(capacity),
+            capacity,
+            end: 0,
         }
     }
 
-    #[test]
-    fn test_shift_array() {
-        let mut a = ShiftArray::new(vec![2, 3, 5, 7, 11]);
-        a.set_start(5);
-        assert_eq!(a[6], 3);
-        assert_eq!(a.len(), 5);
-        a[6] = 13;
-        assert_eq!(a[6], 13);
-        for (cnt, v) in (5..).zip(a.iter()) {
-            assert_eq!(v, a[cnt]);
-        }
-        for (i, v) in a.items() {
-            println!("{}: {}", i, v);
-        }
+    /// Returns the number of values the ring keeps.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
     }
 
-    #[test]
-    fn test_repeat_array2() {
-        let repeat_array: RepeatArray<i32> = RepeatArray::new(1, 5);
-        assert_eq!(repeat_array.value, 1);
-        assert_eq!(repeat_array.size, 5);
-        assert_eq!(repeat_array[0], 1);
-        assert_eq!(repeat_array[1], 1);
-        assert_eq!(repeat_array[2], 1);
-        assert_eq!(repeat_array[3], 1);
-        assert_eq!(repeat_array[4], 1);
-        assert_eq!(repeat_array.get(0), 1);
-        assert_eq!(repeat_array.get(1), 1);
-        assert_eq!(repeat_array.get(2), 1);
-        assert_eq!(repeat_array.get(3), 1);
-        assert_eq!(repeat_array.get(4), 1);
-        for i in repeat_array.iter() {
-            assert_eq!(i, 1);
-        }
+    /// Returns the number of values currently held.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.buf.len()
     }
 
-    #[test]
-    fn test_shift_array2() {
-        let mut shift_array: ShiftArray<i32> = ShiftArray::new(vec![1, 2, 3, 4, 5]);
-        shift_array.set_start(3);
-        assert_eq!(shift_array[6], 4);
-        assert_eq!(shift_array[7], 5);
-        shift_array[6] = 8;
-        assert_eq!(shift_array[6], 8);
-        for (i, v) in shift_array.items() {
-            assert_eq!(v, &shift_array[i]);
-        }
+    /// Returns `true` if nothing has been pushed yet.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buf.is_empty()
     }
 
-    #[test]
-    fn test_repeat_array_empty_and_iterator() {
-        let arr: RepeatArray<i32> = RepeatArray::new(42, 0);
-        assert!(arr.is_empty());
+    /// Returns the logical index of the oldest value still held.
+    #[inline]
+    pub fn start(&self) -> usize {
+        self.end - self.buf.len()
+    }
+
+    /// Returns the logical index the next pushed value will get.
+    #[inline]
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// Append `value` at logical index `end()`, overwriting the oldest value
+    /// once the ring is full.
+    ///
+    /// Returns:
+    ///
+    /// The overwritten value, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::RingArray;
+    /// let mut ring = RingArray::new(2);
+    /// assert_eq!(ring.push_back('a'), None);
+    /// assert_eq!(ring.push_back('b'), None);
+    /// assert_eq!(ring.push_back('c'), Some('a'));
+    /// assert_eq!(ring.start(), 1);
+    /// assert_eq!(ring.get(0), None);
+    /// assert_eq!(ring.get(2), Some(&'c'));
+    /// ```
+    pub fn push_back(&mut self, value: T) -> Option<T> {
+        let slot = self.end % self.capacity;
+        self.end += 1;
+        if self.buf.len() < self.capacity {
+            self.buf.push(value);
+            None
+        } else {
+            Some(std::mem::replace(&mut self.buf[slot], value))
+        }
+    }
+
+    /// Get a reference to the value at logical index `index`, or `None` if it
+    /// has been overwritten or not pushed yet.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if self.contains(index) {
+            Some(&self.buf[index % self.capacity])
+        } else {
+            None
+        }
+    }
+
+    /// Get a mutable reference to the value at logical index `index`, or
+    /// `None` if it has been overwritten or not pushed yet.
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        if self.contains(index) {
+            Some(&mut self.buf[index % self.capacity])
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if logical index `index` is still held.
+    #[inline]
+    pub fn contains(&self, index: usize) -> bool {
+        index >= self.start() && index < self.end
+    }
+
+    /// Returns an iterator over `(logical index, &value)` pairs, oldest first,
+    /// like [`ShiftArray::items`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::RingArray;
+    /// let mut ring = RingArray::new(3);
+    /// for v in 10..15 {
+    ///     ring.push_back(v);
+    /// }
+    /// let items: Vec<_> = ring.items().map(|(i, v)| (i, *v)).collect();
+    /// assert_eq!(items, vec![(2, 12), (3, 13), (4, 14)]);
+    /// ```
+    pub fn items(&self) -> impl Iterator<Item = (usize, &T)> {
+        (self.start()..self.end).map(move |i| (i, &self.buf[i % self.capacity]))
+    }
+
+    /// Returns an iterator over the values, oldest first.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::RingArray;
+    /// let mut ring = RingArray::new(3);
+    /// for v in 1..=4 {
+    ///     ring.push_back(v);
+    /// }
+    /// assert_eq!(ring.iter().copied().collect::<Vec<_>>(), vec![2, 3, 4]);
+    /// assert_eq!(ring.iter().rev().next(), Some(&4));
+    /// ```
+    pub fn iter(&self) -> std::iter::Chain<std::slice::Iter<'_, T>, std::slice::Iter<'_, T>> {
+        let (older, newer) = self.halves();
+        older.iter().chain(newer.iter())
+    }
+
+    /// Removes all values and restarts logical indices at 0.
+    pub fn clear(&mut self) {
+        self.buf.clear();
+        self.end = 0;
+    }
+
+    /// Split the slots into the older and newer runs, in logical order.
+    fn halves(&self) -> (&[T], &[T]) {
+        if self.buf.len() < self.capacity {
+            (&self.buf[..], &[])
+        } else {
+            let (newer, older) = self.buf.split_at(self.end % self.capacity);
+            (older, newer)
+        }
+    }
+}
+
+impl<T> std::ops::Index<usize> for RingArray<T> {
+    type Output = T;
+
+    /// Returns the value at logical index `index`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` has been overwritten or not pushed yet.
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.get(index) {
+            Some(v) => v,
+            None => panic!(
+                "logical index {index} is outside {}..{}",
+                self.start(),
+                self.end
+            ),
+        }
+    }
+}
+
+impl<T> std::ops::IndexMut<usize> for RingArray<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        let (start, end) = (self.start(), self.end);
+        match self.get_mut(index) {
+            Some(v) => v,
+            None => panic!("logical index {index} is outside {start}..{end}"),
+        }
+    }
+}
+
+/// The `ShiftGrid` type is a two-dimensional [`ShiftArray`]: a row-major grid
+/// addressed by absolute `(row, col)` coordinates starting at an arbitrary
+/// origin.
+///
+/// ```svgbob
+///              col_start          col_start + num_cols
+///                 |                   |
+///   row_start --> +----+----+----+
+///                 |  0 |  1 |  2 |
+///                 +----+----+----+    data = [0, 1, 2, 3, 4, 5]
+///                 |  3 |  4 |  5 |
+///                 +----+----+----+
+/// ```
+///
+/// # Performance Characteristics
+///
+/// * **Time Complexity**: O(1) for access and translation, O(n) for iteration
+/// * **Space Complexity**: O(rows * cols) in a single `Vec`
+/// * **Memory Overhead**: Minimal - origin and shape + Vec pointer
+/// * **Cache Performance**: Rows are contiguous slices; columns are strided
+/// * **Use Cases**: Placement grids, routing grids, image tiles
+///
+/// Properties:
+///
+/// * `row_start`, `col_start`: The coordinates of the top-left cell.
+/// * `num_rows`, `num_cols`: The shape of the grid.
+/// * `data`: The cells in row-major order.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ShiftGrid<T> {
+    row_start: usize,
+    col_start: usize,
+    num_rows: usize,
+    num_cols: usize,
+    data: Vec<T>,
+}
+
+impl<T> ShiftGrid<T> {
+    /// Creates a `num_rows` by `num_cols` grid with origin `(0, 0)`, every
+    /// cell set to `fill`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_cols` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::ShiftGrid;
+    /// let mut grid = ShiftGrid::new(2, 3, 0);
+    /// grid.set_origin(100, 40);
+    /// grid[(101, 42)] = 7;
+    /// assert_eq!(grid.get((101, 42)), Some(&7));
+    /// assert_eq!(grid.get((0, 0)), None);
+    /// ```
+    pub fn new(num_rows: usize, num_cols: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_vec(num_rows, num_cols, vec![fill; num_rows * num_cols])
+    }
+
+    /// Creates a `num_rows` by `num_cols` grid with origin `(0, 0)` from
+    /// row-major `data`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_cols` is zero or `data.len() != num_rows * num_cols`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::ShiftGrid;
+    /// let grid = ShiftGrid::from_vec(2, 3, (0..6).collect::<Vec<i32>>());
+    /// assert_eq!(grid.row(1), &[3, 4, 5]);
+    /// assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![2, 5]);
+    /// ```
+    pub fn from_vec(num_rows: usize, num_cols: usize, data: Vec<T>) -> Self {
+        assert!(num_cols > 0);
+        assert_eq!(data.len(), num_rows * num_cols);
+        Self {
+            row_start: 0,
+            col_start: 0,
+            num_rows,
+            num_cols,
+            data,
+        }
+    }
+
+    /// Returns the coordinates `(row_start, col_start)` of the top-left cell.
+    #[inline]
+    pub fn origin(&self) -> (usize, usize) {
+        (self.row_start, self.col_start)
+    }
+
+    /// Returns the number of rows.
+    #[inline]
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Returns the number of columns.
+    #[inline]
+    pub fn num_cols(&self) -> usize {
+        self.num_cols
+    }
+
+    /// Returns the cells in row-major order.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Move the top-left cell to `(row, col)`. No cells are copied.
+    pub fn set_origin(&mut self, row: usize, col: usize) {
+        self.row_start = row;
+        self.col_start = col;
+    }
+
+    /// Move the origin by `(d_row, d_col)`. No cells are copied: the cell at
+    /// `(r, c)` is afterwards found at `(r + d_row, c + d_col)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new origin would be negative or overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::ShiftGrid;
+    /// let mut grid = ShiftGrid::from_vec(1, 2, vec!['a', 'b']);
+    /// grid.set_origin(5, 5);
+    /// grid.translate(-2, 3);
+    /// assert_eq!(grid.origin(), (3, 8));
+    /// assert_eq!(grid[(3, 9)], 'b');
+    /// ```
+    pub fn translate(&mut self, d_row: isize, d_col: isize) {
+        self.row_start = shift_by(self.row_start, d_row);
+        self.col_start = shift_by(self.col_start, d_col);
+    }
+
+    /// Get a reference to the cell at `(row, col)`, if it lies in the grid.
+    #[inline]
+    pub fn get(&self, index: (usize, usize)) -> Option<&T> {
+        self.position(index).map(|i| &self.data[i])
+    }
+
+    /// Get a mutable reference to the cell at `(row, col)`, if it lies in the grid.
+    #[inline]
+    pub fn get_mut(&mut self, index: (usize, usize)) -> Option<&mut T> {
+        self.position(index).map(move |i| &mut self.data[i])
+    }
+
+    /// Returns the cells of row `row` as a slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` lies outside the grid.
+    pub fn row(&self, row: usize) -> &[T] {
+        let i = self.row_position(row);
+        &self.data[i * self.num_cols..(i + 1) * self.num_cols]
+    }
+
+    /// Returns the cells of row `row` as a mutable slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` lies outside the grid.
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        let i = self.row_position(row);
+        &mut self.data[i * self.num_cols..(i + 1) * self.num_cols]
+    }
+
+    /// Returns an iterator over the cells of column `col`, top to bottom.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `col` lies outside the grid.
+    pub fn column(&self, col: usize) -> std::iter::StepBy<std::slice::Iter<'_, T>> {
+        let j = self.col_position(col);
+        self.data
+            .get(j..)
+            .unwrap_or(&[])
+            .iter()
+            .step_by(self.num_cols)
+    }
+
+    /// Returns a mutable iterator over the cells of column `col`, top to bottom.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `col` lies outside the grid.
+    pub fn column_mut(&mut self, col: usize) -> std::iter::StepBy<std::slice::IterMut<'_, T>> {
+        let j = self.col_position(col);
+        self.data
+            .get_mut(j..)
+            .unwrap_or(&mut [])
+            .iter_mut()
+            .step_by(self.num_cols)
+    }
+
+    /// Returns an iterator over the rows as slices, top to bottom.
+    pub fn rows(&self) -> std::slice::ChunksExact<'_, T> {
+        self.data.chunks_exact(self.num_cols)
+    }
+
+    /// Returns a mutable iterator over the rows as slices, top to bottom.
+    pub fn rows_mut(&mut self) -> std::slice::ChunksExactMut<'_, T> {
+        self.data.chunks_exact_mut(self.num_cols)
+    }
+
+    /// Returns an iterator over `((row, col), &cell)` in row-major order,
+    /// like [`ShiftArray::items`].
+    pub fn items(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.data.iter().enumerate().map(move |(i, v)| {
+            let (r, c) = (i / self.num_cols, i % self.num_cols);
+            ((self.row_start + r, self.col_start + c), v)
+        })
+    }
+
+    /// Returns a read-only view of the cells in `rows` x `cols`, addressed
+    /// by the same absolute coordinates as the grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the window is not contained in the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::ShiftGrid;
+    /// let mut grid = ShiftGrid::from_vec(3, 3, (0..9).collect::<Vec<i32>>());
+    /// grid.set_origin(10, 20);
+    /// let view = grid.window(11..13, 21..23);
+    /// assert_eq!(view[(11, 21)], 4);
+    /// assert_eq!(view.get((10, 21)), None);
+    /// assert_eq!(view.rows().collect::<Vec<_>>(), vec![&[4, 5][..], &[7, 8][..]]);
+    /// ```
+    pub fn window(&self, rows: Range<usize>, cols: Range<usize>) -> GridView<'_, T> {
+        self.check_window(&rows, &cols);
+        GridView {
+            grid: self,
+            rows,
+            cols,
+        }
+    }
+
+    /// Returns a mutable view of the cells in `rows` x `cols`, addressed by
+    /// the same absolute coordinates as the grid.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the window is not contained in the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::array_like::ShiftGrid;
+    /// let mut grid = ShiftGrid::new(3, 3, 0);
+    /// grid.window_mut(1..3, 0..2).fill(1);
+    /// assert_eq!(grid.as_slice(), &[0, 0, 0, 1, 1, 0, 1, 1, 0]);
+    /// ```
+    pub fn window_mut(&mut self, rows: Range<usize>, cols: Range<usize>) -> GridViewMut<'_, T> {
+        self.check_window(&rows, &cols);
+        GridViewMut {
+            grid: self,
+            rows,
+            cols,
+        }
+    }
+
+    fn check_window(&self, rows: &Range<usize>, cols: &Range<usize>) {
+        assert!(rows.start <= rows.end && cols.start <= cols.end);
+        assert!(
+            rows.start >= self.row_start && rows.end <= self.row_start + self.num_rows,
+            "window rows {rows:?} lie outside the grid"
+        );
+        assert!(
+            cols.start >= self.col_start && cols.end <= self.col_start + self.num_cols,
+            "window columns {cols:?} lie outside the grid"
+        );
+    }
+
+    /// Translate absolute coordinates into a position in `data`.
+    #[inline]
+    fn position(&self, (row, col): (usize, usize)) -> Option<usize> {
+        let r = row
+            .checked_sub(self.row_start)
+            .filter(|&r| r < self.num_rows)?;
+        let c = col
+            .checked_sub(self.col_start)
+            .filter(|&c| c < self.num_cols)?;
+        Some(r * self.num_cols + c)
+    }
+
+    fn row_position(&self, row: usize) -> usize {
+        match row
+            .checked_sub(self.row_start)
+            .filter(|&r| r < self.num_rows)
+        {
+            Some(r) => r,
+            None => panic!("row {row} lies outside the grid"),
+        }
+    }
+
+    fn col_position(&self, col: usize) -> usize {
+        match col
+            .checked_sub(self.col_start)
+            .filter(|&c| c < self.num_cols)
+        {
+            Some(c) => c,
+            None => panic!("column {col} lies outside the grid"),
+        }
+    }
+}
+
+/// Offset `x` by the signed delta `d`.
+fn shift_by(x: usize, d: isize) -> usize {
+    let shifted = if d >= 0 {
+        x.checked_add(d as usize)
+    } else {
+        x.checked_sub(d.unsigned_abs())
+    };
+    shifted.expect("grid origin out of range")
+}
+
+impl<T> std::ops::Index<(usize, usize)> for ShiftGrid<T> {
+    type Output = T;
+
+    /// Returns the cell at absolute coordinates `(row, col)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `(row, col)` lies outside the grid.
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        match self.position(index) {
+            Some(i) => &self.data[i],
+            None => panic!("cell {index:?} lies outside the grid"),
+        }
+    }
+}
+
+impl<T> std::ops::IndexMut<(usize, usize)> for ShiftGrid<T> {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        match self.position(index) {
+            Some(i) => &mut self.data[i],
+            None => panic!("cell {index:?} lies outside the grid"),
+        }
+    }
+}
+
+/// A read-only rectangular window into a [`ShiftGrid`], created by
+/// [`ShiftGrid::window`].
+pub struct GridView<'a, T> {
+    grid: &'a ShiftGrid<T>,
+    rows: Range<usize>,
+    cols: Range<usize>,
+}
+
+impl<'a, T> GridView<'a, T> {
+    /// Returns the coordinates of the top-left cell of the window.
+    #[inline]
+    pub fn origin(&self) -> (usize, usize) {
+        (self.rows.start, self.cols.start)
+    }
+
+    /// Returns the number of rows in the window.
+    #[inline]
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns the number of columns in the window.
+    #[inline]
+    pub fn num_cols(&self) -> usize {
+        self.cols.len()
+    }
+
+    /// Get a reference to the cell at `(row, col)`, if it lies in the window.
+    #[inline]
+    pub fn get(&self, (row, col): (usize, usize)) -> Option<&'a T> {
+        if self.rows.contains(&row) && self.cols.contains(&col) {
+            self.grid.get((row, col))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the part of row `row` inside the window.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` lies outside the window.
+    pub fn row(&self, row: usize) -> &'a [T] {
+        assert!(
+            self.rows.contains(&row),
+            "row {row} lies outside the window"
+        );
+        let offset = self.cols.start - self.grid.col_start;
+        &self.grid.row(row)[offset..offset + self.cols.len()]
+    }
+
+    /// Returns an iterator over the rows of the window as slices.
+    pub fn rows(&self) -> impl Iterator<Item = &'a [T]> + '_ {
+        self.rows.clone().map(move |r| self.row(r))
+    }
+}
+
+impl<'a, T> std::ops::Index<(usize, usize)> for GridView<'a, T> {
+    type Output = T;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        match self.get(index) {
+            Some(v) => v,
+            None => panic!("cell {index:?} lies outside the window"),
+        }
+    }
+}
+
+/// A mutable rectangular window into a [`ShiftGrid`], created by
+/// [`ShiftGrid::window_mut`].
+pub struct GridViewMut<'a, T> {
+    grid: &'a mut ShiftGrid<T>,
+    rows: Range<usize>,
+    cols: Range<usize>,
+}
+
+impl<'a, T> GridViewMut<'a, T> {
+    /// Returns the coordinates of the top-left cell of the window.
+    #[inline]
+    pub fn origin(&self) -> (usize, usize) {
+        (self.rows.start, self.cols.start)
+    }
+
+    /// Returns the number of rows in the window.
+    #[inline]
+    pub fn num_rows(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns the number of columns in the window.
+    #[inline]
+    pub fn num_cols(&self) -> usize {
+        self.cols.len()
+    }
+
+    /// Get a reference to the cell at `(row, col)`, if it lies in the window.
+    #[inline]
+    pub fn get(&self, (row, col): (usize, usize)) -> Option<&T> {
+        if self.rows.contains(&row) && self.cols.contains(&col) {
+            self.grid.get((row, col))
+        } else {
+            None
+        }
+    }
+
+    /// Get a mutable reference to the cell at `(row, col)`, if it lies in the window.
+    #[inline]
+    pub fn get_mut(&mut self, (row, col): (usize, usize)) -> Option<&mut T> {
+        if self.rows.contains(&row) && self.cols.contains(&col) {
+            self.grid.get_mut((row, col))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the part of row `row` inside the window as a mutable slice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` lies outside the window.
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        assert!(
+            self.rows.contains(&row),
+            "row {row} lies outside the window"
+        );
+        let offset = self.cols.start - self.grid.col_start;
+        let width = self.cols.len();
+        &mut self.grid.row_mut(row)[offset..offset + width]
+    }
+
+    /// Set every cell of the window to `value`.
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        for r in self.rows.clone() {
+            self.row_mut(r).fill(value.clone());
+        }
+    }
+}
+
+impl<'a, T> std::ops::Index<(usize, usize)> for GridViewMut<'a, T> {
+    type Output = T;
+
+    fn index(&self, index: (usize, usize)) -> &Self::Output {
+        match self.get(index) {
+            Some(v) => v,
+            None => panic!("cell {index:?} lies outside the window"),
+        }
+    }
+}
+
+impl<'a, T> std::ops::IndexMut<(usize, usize)> for GridViewMut<'a, T> {
+    fn index_mut(&mut self, index: (usize, usize)) -> &mut Self::Output {
+        match self.get_mut(index) {
+            Some(v) => v,
+            None => panic!("cell {index:?} lies outside the window"),
+        }
+    }
+}
+
+/// Common read-only interface of the array-like containers.
+///
+/// Lets an algorithm take, e.g., vertex weights as a [`RepeatArray`] for unit
+/// weights or a `Vec` for real ones without being written twice. Indices are
+/// positions `0..len()`; for a [`ShiftArray`] or [`RingArray`] that is the
+/// position from the first live element, not the shifted or logical index
+/// taken by their inherent `get`. The accessors are named `get_pos` and
+/// `get_pos_mut` so the two never shadow each other.
+///
+/// `get_pos` follows the standard library in returning `None` out of bounds.
+///
+/// # Examples
+///
+/// ```rust
+/// use mywheel_rs::array_like::{ArrayLike, RepeatArray};
+///
+/// fn total_weight<A: ArrayLike<u32> + ?Sized>(weights: &A) -> u32 {
+///     weights.iter().sum()
+/// }
+///
+/// assert_eq!(total_weight(&RepeatArray::new(1, 4)), 4);
+/// assert_eq!(total_weight(&vec![3, 4, 5]), 12);
+/// assert_eq!(total_weight(&[3, 4][..]), 7);
+/// ```
+pub trait ArrayLike<T> {
+    /// Returns the number of elements.
+    fn len(&self) -> usize;
+
+    /// Returns a reference to the element at position `i`, or `None` if out of bounds.
+    fn get_pos(&self, i: usize) -> Option<&T>;
+
+    /// Returns `true` if there are no elements.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over references to the elements in position order.
+    #[inline]
+    fn iter(&self) -> ArrayLikeIter<'_, T, Self> {
+        ArrayLikeIter {
+            array: self,
+            front: 0,
+            back: self.len(),
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Mutable counterpart of [`ArrayLike`] for containers that own distinct elements.
+///
+/// # Examples
+///
+/// ```rust
+/// use mywheel_rs::array_like::{ArrayLikeMut, ShiftArray};
+///
+/// fn double<A: ArrayLikeMut<i32> + ?Sized>(a: &mut A) {
+///     for i in 0..a.len() {
+///         *a.get_pos_mut(i).unwrap() *= 2;
+///     }
+/// }
+///
+/// let mut v = vec![1, 2];
+/// double(&mut v);
+/// assert_eq!(v, vec![2, 4]);
+///
+/// let mut s = ShiftArray::new(vec![5, 6]);
+/// s.set_start(10);
+/// double(&mut s);
+/// assert_eq!(s[11], 12);
+/// ```
+pub trait ArrayLikeMut<T>: ArrayLike<T> {
+    /// Returns a mutable reference to the element at position `i`, or `None` if out of bounds.
+    fn get_pos_mut(&mut self, i: usize) -> Option<&mut T>;
+}
+
+/// Iterator over any [`ArrayLike`] container, created by [`ArrayLike::iter`].
+pub struct ArrayLikeIter<'a, T, A: ?Sized> {
+    array: &'a A,
+    front: usize,
+    back: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, T: 'a, A: ArrayLike<T> + ?Sized> Iterator for ArrayLikeIter<'a, T, A> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.front += 1;
+            self.array.get_pos(self.front - 1)
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.back - self.front;
+        (n, Some(n))
+    }
+}
+
+impl<'a, T: 'a, A: ArrayLike<T> + ?Sized> DoubleEndedIterator for ArrayLikeIter<'a, T, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front < self.back {
+            self.back -= 1;
+            self.array.get_pos(self.back)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T: 'a, A: ArrayLike<T> + ?Sized> ExactSizeIterator for ArrayLikeIter<'a, T, A> {}
+
+impl<T> ArrayLike<T> for RepeatArray<T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    fn get_pos(&self, i: usize) -> Option<&T> {
+        if i < self.size {
+            Some(&self.value)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T, S> ArrayLike<T> for ShiftArray<T, S> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    #[inline]
+    fn get_pos(&self, i: usize) -> Option<&T> {
+        self.as_slice().get(i)
+    }
+}
+
+impl<T, S> ArrayLikeMut<T> for ShiftArray<T, S> {
+    #[inline]
+    fn get_pos_mut(&mut self, i: usize) -> Option<&mut T> {
+        self.as_mut_slice().get_mut(i)
+    }
+}
+
+impl<T> ArrayLike<T> for RingArray<T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.buf.len()
+    }
+
+    #[inline]
+    fn get_pos(&self, i: usize) -> Option<&T> {
+        self.start()
+            .checked_add(i)
+            .and_then(|k| RingArray::get(self, k))
+    }
+}
+
+impl<T> ArrayLikeMut<T> for RingArray<T> {
+    #[inline]
+    fn get_pos_mut(&mut self, i: usize) -> Option<&mut T> {
+        self.start()
+            .checked_add(i)
+            .and_then(move |k| RingArray::get_mut(self, k))
+    }
+}
+
+impl<T> ArrayLike<T> for MapAdapter<T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.lst.len()
+    }
+
+    #[inline]
+    fn get_pos(&self, i: usize) -> Option<&T> {
+        self.lst.get(i)
+    }
+}
+
+impl<T> ArrayLikeMut<T> for MapAdapter<T> {
+    #[inline]
+    fn get_pos_mut(&mut self, i: usize) -> Option<&mut T> {
+        self.lst.get_mut(i)
+    }
+}
+
+impl<T> ArrayLike<T> for Vec<T> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    #[inline]
+    fn get_pos(&self, i: usize) -> Option<&T> {
+        self.as_slice().get(i)
+    }
+}
+
+impl<T> ArrayLikeMut<T> for Vec<T> {
+    #[inline]
+    fn get_pos_mut(&mut self, i: usize) -> Option<&mut T> {
+        self.as_mut_slice().get_mut(i)
+    }
+}
+
+impl<T> ArrayLike<T> for [T] {
+    #[inline]
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    #[inline]
+    fn get_pos(&self, i: usize) -> Option<&T> {
+        <[T]>::get(self, i)
+    }
+}
+
+impl<T> ArrayLikeMut<T> for [T] {
+    #[inline]
+    fn get_pos_mut(&mut self, i: usize) -> Option<&mut T> {
+        <[T]>::get_mut(self, i)
+    }
+}
+
+/// Display implementation for RepeatArray.
+///
+/// Formats as: `RepeatArray[size] with value: {value}`
+impl<T: std::fmt::Display> std::fmt::Display for RepeatArray<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RepeatArray[{}] with value: {}", self.size, self.value)
+    }
+}
+
+/// Debug implementation for RepeatArray.
+impl<T: std::fmt::Debug> std::fmt::Debug for RepeatArray<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RepeatArray")
+            .field("value", &self.value)
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+/// Debug implementation for ShiftGrid.
+impl<T: std::fmt::Debug> std::fmt::Debug for ShiftGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShiftGrid")
+            .field("origin", &self.origin())
+            .field("num_rows", &self.num_rows)
+            .field("num_cols", &self.num_cols)
+            .finish()
+    }
+}
+
+/// Debug implementation for RingArray.
+impl<T: std::fmt::Debug> std::fmt::Debug for RingArray<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RingArray")
+            .field("start", &self.start())
+            .field("end", &self.end)
+            .field("capacity", &self.capacity)
+            .finish()
+    }
+}
+
+/// Debug implementation for ShiftArray.
+impl<T: std::fmt::Debug, S: std::fmt::Debug> std::fmt::Debug for ShiftArray<T, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShiftArray")
+            .field("start", &self.start)
+            .field("len", &self.as_slice().len())
+            .finish()
+    }
+}
+
+impl<T: PartialEq, S: PartialEq> PartialEq for ShiftArray<T, S> {
+    /// Two arrays are equal if they have the same `start` and elements,
+    /// regardless of hidden front capacity.
+    fn eq(&self, other: &Self) -> bool {
+        self.start == other.start && self.as_slice() == other.as_slice()
+    }
+}
+
+impl<T: Eq, S: Eq> Eq for ShiftArray<T, S> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeat_array() {
+        let arr: RepeatArray<i32> = RepeatArray::new(1, 10);
+        assert_eq!(arr.len(), 10);
+        assert_eq!(arr[4], 1);
+        for i in arr.iter() {
+            assert_eq!(i, 1);
+        }
+    }
+
+    #[test]
+    fn test_shift_array() {
+        let mut a = ShiftArray::new(vec![2, 3, 5, 7, 11]);
+        a.set_start(5);
+        assert_eq!(a[6], 3);
+        assert_eq!(a.len(), 5);
+        a[6] = 13;
+        assert_eq!(a[6], 13);
+        assert_eq!(a.iter().count(), 5);
+        for (cnt, v) in (5..).zip(a.iter()) {
+            assert_eq!(v, a[cnt]);
+        }
+        for (i, v) in a.items() {
+            println!("{}: {}", i, v);
+        }
+    }
+
+    #[test]
+    fn test_repeat_array2() {
+        let repeat_array: RepeatArray<i32> = RepeatArray::new(1, 5);
+        assert_eq!(repeat_array.value, 1);
+        assert_eq!(repeat_array.size, 5);
+        assert_eq!(repeat_array[0], 1);
+        assert_eq!(repeat_array[1], 1);
+        assert_eq!(repeat_array[2], 1);
+        assert_eq!(repeat_array[3], 1);
+        assert_eq!(repeat_array[4], 1);
+        assert_eq!(repeat_array.get(0), 1);
+        assert_eq!(repeat_array.get(1), 1);
+        assert_eq!(repeat_array.get(2), 1);
+        assert_eq!(repeat_array.get(3), 1);
+        assert_eq!(repeat_array.get(4), 1);
+        for i in repeat_array.iter() {
+            assert_eq!(i, 1);
+        }
+    }
+
+    #[test]
+    fn test_shift_array2() {
+        let mut shift_array: ShiftArray<i32> = ShiftArray::new(vec![1, 2, 3, 4, 5]);
+        shift_array.set_start(3);
+        assert_eq!(shift_array[6], 4);
+        assert_eq!(shift_array[7], 5);
+        shift_array[6] = 8;
+        assert_eq!(shift_array[6], 8);
+        for (i, v) in shift_array.items() {
+            assert_eq!(v, &shift_array[i]);
+        }
+    }
+
+    #[test]
+    fn test_repeat_array_empty_and_iterator() {
+        let arr: RepeatArray<i32> = RepeatArray::new(42, 0);
+        assert!(arr.is_empty());
         assert_eq!(arr.len(), 0);
         let mut iter = arr.iter();
         assert_eq!(iter.len(), 0);
@@ -490,4 +1919,307 @@ mod tests {
         assert!(a.is_empty());
         assert_eq!(a.len(), 0);
     }
+
+    /// One generic routine, written once against the trait
+    fn weighted_sum<W: ArrayLike<u32> + ?Sized>(weights: &W, xs: &[u32]) -> u32 {
+        assert_eq!(weights.len(), xs.len());
+        weights.iter().zip(xs).map(|(w, x)| w * x).sum()
+    }
+
+    fn reverse_in_place<A: ArrayLikeMut<u32> + ?Sized>(a: &mut A) {
+        let n = a.len();
+        for i in 0..n / 2 {
+            let x = *a.get_pos(i).unwrap();
+            let y = std::mem::replace(a.get_pos_mut(n - 1 - i).unwrap(), x);
+            *a.get_pos_mut(i).unwrap() = y;
+        }
+    }
+
+    #[test]
+    fn test_array_like_generic() {
+        let xs = [1, 2, 3];
+        let vec = vec![2, 2, 2];
+        let mut shift = ShiftArray::new(vec![2, 2, 2]);
+        shift.set_start(7);
+        assert_eq!(weighted_sum(&RepeatArray::new(2, 3), &xs), 12);
+        assert_eq!(weighted_sum(&shift, &xs), 12);
+        assert_eq!(weighted_sum(&MapAdapter::new(vec.clone()), &xs), 12);
+        assert_eq!(weighted_sum(&vec, &xs), 12);
+        assert_eq!(weighted_sum(&vec[..], &xs), 12);
+
+        let rep = RepeatArray::new(5u32, 2);
+        assert_eq!(rep.get_pos(2), None);
+        assert_eq!(ArrayLike::iter(&rep).rev().len(), 2);
+        assert!(ArrayLike::is_empty(&Vec::<u32>::new()));
+    }
+
+    #[test]
+    fn test_array_like_mut_generic() {
+        let mut vec = vec![1, 2, 3];
+        let mut slice_backing = vec![1, 2, 3];
+        let mut shift = ShiftArray::new(vec![1, 2, 3]);
+        let mut map = MapAdapter::new(vec![1, 2, 3]);
+        reverse_in_place(&mut vec);
+        reverse_in_place(&mut slice_backing[..]);
+        reverse_in_place(&mut shift);
+        reverse_in_place(&mut map);
+        assert_eq!(vec, vec![3, 2, 1]);
+        assert_eq!(slice_backing, vec![3, 2, 1]);
+        assert_eq!(shift.as_slice(), &[3, 2, 1]);
+        assert_eq!(map.lst, vec![3, 2, 1]);
+        assert_eq!(vec.get_pos_mut(3), None);
+    }
+
+    #[test]
+    fn test_shift_array_container() {
+        let mut a = ShiftArray::new(vec![1, 2, 3]);
+        a.set_start(4);
+        a.extend([4, 5]);
+        a.push(6);
+        assert_eq!(a.get(9), Some(&6));
+        assert_eq!(a.get(3), None);
+        assert_eq!(a.pop(), Some(6));
+        for v in &mut a {
+            *v += 1;
+        }
+        assert_eq!(
+            (&a).into_iter().copied().collect::<Vec<_>>(),
+            vec![2, 3, 4, 5, 6]
+        );
+        assert_eq!(a.slice(5..=6), &[3, 4]);
+        assert_eq!(a.slice(6..6), &[] as &[i32]);
+        assert_eq!(a.slice(..), &[2, 3, 4, 5, 6]);
+        assert_eq!(a.into_iter().sum::<i32>(), 20);
+    }
+
+    #[test]
+    #[should_panic(expected = "below the array start")]
+    fn test_shift_array_slice_below_start() {
+        let mut a = ShiftArray::new(vec![1, 2, 3]);
+        a.set_start(4);
+        a.slice(3..5);
+    }
+
+    #[test]
+    fn test_shift_array_signed() {
+        let mut gains: ShiftArray<i32, i32> = ShiftArray::with_start(vec![0; 7], -3);
+        for g in -3..=3 {
+            gains[g] = g * 10;
+        }
+        assert_eq!(gains.as_slice(), &[-30, -20, -10, 0, 10, 20, 30]);
+        assert_eq!(gains.get(-4), None);
+        assert_eq!(gains.get(4), None);
+        assert_eq!(gains.slice(-1..=1), &[-10, 0, 10]);
+        assert_eq!(gains.slice(2..), &[20, 30]);
+        let keys: Vec<i32> = gains.items().map(|(g, _)| g).collect();
+        assert_eq!(keys, (-3..=3).collect::<Vec<_>>());
+
+        let wide: ShiftArray<u8, i64> = ShiftArray::with_start(vec![1, 2], i64::MIN);
+        assert_eq!(wide[i64::MIN + 1], 2);
+        assert_eq!(wide.get(0), None);
+        assert_eq!(wide.get(i64::MAX), None);
+        assert_eq!(i64::MAX.offset_from(i64::MIN), None);
+        assert_eq!((-1i64).offset_from(i64::MIN), Some(i64::MAX as usize));
+        assert_eq!(i32::MIN.offset_from(0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "below the array start")]
+    fn test_shift_array_signed_index_below_start() {
+        let gains: ShiftArray<i32, isize> = ShiftArray::with_start(vec![0; 3], -1);
+        let _ = gains[-2];
+    }
+
+    #[test]
+    fn test_shift_array_set_or_grow() {
+        let mut a: ShiftArray<i32, i32> = ShiftArray::with_start(vec![], 0);
+        a.set_or_grow(0, 1, 0);
+        assert_eq!(a.as_slice(), &[1]);
+        for i in 1..=100 {
+            a.set_or_grow(-i, -i, 0);
+            assert_eq!(a.start, -i);
+            assert_eq!(a.len(), i as usize + 1);
+        }
+        // Walking the front down reallocates only when the slack runs out.
+        assert!(a.buf.capacity() < 256);
+        for i in 0..=100 {
+            assert_eq!(a[-i], if i == 0 { 1 } else { -i });
+        }
+        assert_eq!(a.items().next(), Some((-100, &-100)));
+        a.trim_front(100);
+        assert_eq!(a.start, 0);
+        assert_eq!(a.as_slice(), &[1]);
+        assert_eq!(a.iter().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(a.pop(), Some(1));
+        assert_eq!(a.pop(), None);
+
+        // A single step below the lowest value of the index type is fine:
+        // the slack is hidden, not addressed by `S`.
+        let mut b = ShiftArray::with_start(vec![7, 8, 9], 1usize);
+        b.set_or_grow(0, 5, 0);
+        assert_eq!(b.start, 0);
+        assert_eq!(b.as_slice(), &[5, 7, 8, 9]);
+        b.set_or_grow(6, 6, 0);
+        assert_eq!(b.as_slice(), &[5, 7, 8, 9, 0, 0, 6]);
+    }
+
+    #[test]
+    fn test_shift_array_trim_front_is_lazy() {
+        let mut a = ShiftArray::new((0..10).collect::<Vec<i32>>());
+        let ptr = a.as_slice().as_ptr();
+        a.trim_front(3);
+        assert_eq!(a.start, 3);
+        assert_eq!(a.len(), 7);
+        assert_eq!(a.as_slice(), &[3, 4, 5, 6, 7, 8, 9]);
+        // No element moved yet.
+        assert_eq!(a.as_slice().as_ptr(), ptr.wrapping_add(3));
+        // Writing below `start` reuses the trimmed slots.
+        a.set_or_grow(1, -1, 0);
+        assert_eq!(a.as_slice(), &[-1, 0, 3, 4, 5, 6, 7, 8, 9]);
+        assert_eq!(a.as_slice().as_ptr(), ptr.wrapping_add(1));
+        // Compacts once the hidden slack exceeds twice the length.
+        a.trim_front(7);
+        assert_eq!(a.start, 8);
+        assert_eq!(a.as_slice(), &[8, 9]);
+        assert_eq!(a.head, 0);
+        assert_eq!(a.clone().into_vec(), vec![8, 9]);
+        assert_eq!(a, ShiftArray::with_start(vec![8, 9], 8));
+        a.trim_front(2);
+        assert!(a.is_empty());
+        assert_eq!(a.into_iter().count(), 0);
+    }
+
+    #[test]
+    fn prop_shift_array_set_or_grow_matches_model() {
+        fn prop(writes: Vec<(i8, u8)>, trim: u8) -> bool {
+            let mut a: ShiftArray<u8, i32> = ShiftArray::with_start(vec![], 0);
+            let mut model = std::collections::BTreeMap::new();
+            for &(i, v) in &writes {
+                a.set_or_grow(i as i32, v, 0);
+                model.insert(i as i32, v);
+            }
+            // The array spans exactly the written range and the initial start.
+            let lo = model.keys().next().map_or(0, |&i| i.min(0));
+            let hi = model.keys().next_back().map_or(0, |&i| (i + 1).max(0));
+            if a.start != lo || a.len() != (hi - lo) as usize {
+                return false;
+            }
+            let trim = (trim as usize).min(a.len());
+            a.trim_front(trim);
+            a.start == lo + trim as i32
+                && a.items()
+                    .all(|(i, v)| *v == model.get(&i).copied().unwrap_or(0))
+                && model
+                    .iter()
+                    .filter(|&(&i, _)| i >= a.start)
+                    .all(|(&i, &v)| a.get(i) == Some(&v))
+        }
+        quickcheck::QuickCheck::new().quickcheck(prop as fn(Vec<(i8, u8)>, u8) -> bool);
+    }
+
+    #[test]
+    fn test_ring_array() {
+        let mut ring = RingArray::new(3);
+        assert_eq!(ring.get(0), None);
+        for v in 0..3 {
+            assert_eq!(ring.push_back(v * 10), None);
+        }
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), vec![0, 10, 20]);
+        assert_eq!(ring.push_back(30), Some(0));
+        ring[2] += 1;
+        assert_eq!(ring[2], 21);
+        assert!(!ring.contains(0));
+        assert_eq!(ring.get_pos(0), Some(&10));
+        assert_eq!(
+            ring.iter().rev().copied().collect::<Vec<_>>(),
+            vec![30, 21, 10]
+        );
+        ring.clear();
+        assert!(ring.is_empty());
+        assert_eq!(ring.end(), 0);
+        ring.push_back(40);
+        assert_eq!(ring.items().collect::<Vec<_>>(), vec![(0, &40)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside")]
+    fn test_ring_array_index_evicted() {
+        let mut ring = RingArray::new(1);
+        ring.push_back(1);
+        ring.push_back(2);
+        let _ = ring[0];
+    }
+
+    #[test]
+    fn prop_ring_array_keeps_last_capacity() {
+        fn prop(values: Vec<u8>, capacity: u8) -> bool {
+            let capacity = capacity as usize % 8 + 1;
+            let mut ring = RingArray::new(capacity);
+            for &v in &values {
+                ring.push_back(v);
+            }
+            let keep = values.len().saturating_sub(capacity);
+            ring.iter().eq(values[keep..].iter())
+                && ring.items().all(|(i, v)| values[i] == *v)
+                && (0..values.len() + 2)
+                    .all(|i| ring.get(i).is_some() == (i >= keep && i < values.len()))
+        }
+        quickcheck::QuickCheck::new().quickcheck(prop as fn(Vec<u8>, u8) -> bool);
+    }
+
+    #[test]
+    fn test_shift_grid() {
+        let mut grid = ShiftGrid::from_vec(2, 3, (0..6).collect::<Vec<i32>>());
+        grid.set_origin(4, 7);
+        assert_eq!(grid[(4, 7)], 0);
+        assert_eq!(grid[(5, 9)], 5);
+        assert_eq!(grid.get((6, 7)), None);
+        assert_eq!(grid.get((4, 10)), None);
+        assert_eq!(grid.get((3, 7)), None);
+
+        for v in grid.column_mut(8) {
+            *v *= 10;
+        }
+        assert_eq!(grid.row(4), &[0, 10, 2]);
+        assert_eq!(grid.rows().nth(1), Some(&[3, 40, 5][..]));
+        for row in grid.rows_mut() {
+            row[0] = -1;
+        }
+        assert_eq!(grid.column(7).copied().collect::<Vec<_>>(), vec![-1, -1]);
+
+        grid.translate(1, -7);
+        assert_eq!(grid.origin(), (5, 0));
+        assert_eq!(grid[(6, 1)], 40);
+        let cells: Vec<_> = grid.items().map(|(rc, _)| rc).collect();
+        assert_eq!(cells[..4], [(5, 0), (5, 1), (5, 2), (6, 0)]);
+    }
+
+    #[test]
+    fn test_shift_grid_windows() {
+        let mut grid = ShiftGrid::new(4, 4, 0);
+        grid.set_origin(1, 1);
+        {
+            let mut view = grid.window_mut(2..4, 3..5);
+            assert_eq!((view.num_rows(), view.num_cols()), (2, 2));
+            view.fill(1);
+            view[(3, 4)] = 2;
+            assert!(view.get_mut((1, 3)).is_none());
+            assert_eq!(view.row_mut(2), &[1, 1]);
+        }
+        let view = grid.window(1..5, 3..5);
+        assert_eq!(view.origin(), (1, 3));
+        let rows: Vec<&[i32]> = view.rows().collect();
+        assert_eq!(rows, vec![&[0, 0][..], &[1, 1], &[1, 2], &[0, 0]]);
+        assert_eq!(view.get((1, 2)), None);
+        let empty = grid.window(3..3, 1..5);
+        assert_eq!(empty.rows().count(), 0);
+        assert_eq!(ShiftGrid::new(0, 2, 0).column(1).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "lie outside the grid")]
+    fn test_shift_grid_window_out_of_range() {
+        let grid = ShiftGrid::new(2, 2, 0u8);
+        grid.window(0..3, 0..2);
+    }
 }
@@ -1,4 +1,15 @@
-use crate::dllist::{Dllink, Dllist};
+use crate::dllist::{dot_escape, Dllink, Dllist};
+use std::fmt;
+
+/// Tie order used when linking a batch of nodes into a [`BPQueue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InsertOrder {
+    /// Nodes with equal keys keep their input order (FIFO).
+    #[default]
+    Stable,
+    /// Nodes with equal keys end up in reverse input order (LIFO).
+    Reversed,
+}
 
 #[doc = svgbobdoc::transform!(
 /// The `BPQueue` struct is a bounded priority queue implemented using an array of doubly-linked lists,
@@ -218,6 +229,156 @@ impl<T: Default + Clone> BPQueue<T> {
         self.bucket[it.data.0].append(it);
     }
 
+    /// Construct a new BPQueue object and fill it from (node, key) pairs
+    ///
+    /// Equivalent to [`BPQueue::new`] followed by [`BPQueue::extend_from`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::bpqueue::{BPQueue, InsertOrder};
+    /// use mywheel_rs::dllist::Dllink;
+    ///
+    /// let mut a = Dllink::<(usize, i32)>::new((0, 3));
+    /// let mut b = Dllink::<(usize, i32)>::new((0, 4));
+    /// let bpq = BPQueue::from_iter_with_range(-3, 3, [(&mut a, 1), (&mut b, -2)], InsertOrder::Stable);
+    ///
+    /// assert_eq!(bpq.get_max(), 1);
+    /// ```
+    pub fn from_iter_with_range<'a, I>(a: i32, b: i32, items: I, order: InsertOrder) -> Self
+    where
+        I: IntoIterator<Item = (&'a mut Dllink<(usize, T)>, i32)>,
+        T: 'a,
+    {
+        let mut res = Self::new(a, b);
+        res.extend_from(items, order);
+        res
+    }
+
+    /// Append many items with external keys at once
+    ///
+    /// All keys are validated before any node is linked, each node is then
+    /// linked straight into its bucket, and `max` is updated a single time at
+    /// the end. `order` decides how nodes sharing a key are arranged in their
+    /// bucket, so tie order stays reproducible.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any key lies outside the range given to [`BPQueue::new`].
+    /// In that case no node has been linked.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::bpqueue::{BPQueue, InsertOrder};
+    /// use mywheel_rs::dllist::Dllink;
+    ///
+    /// let mut bpq = BPQueue::<i32>::new(-3, 3);
+    /// let mut a = Dllink::<(usize, i32)>::new((0, 3));
+    /// let mut b = Dllink::<(usize, i32)>::new((0, 4));
+    /// bpq.extend_from([(&mut a, 2), (&mut b, 2)], InsertOrder::Reversed);
+    /// let d = bpq.popleft();
+    ///
+    /// assert_eq!(unsafe { (*d).data.1 }, 4);
+    /// ```
+    pub fn extend_from<'a, I>(&mut self, items: I, order: InsertOrder)
+    where
+        I: IntoIterator<Item = (&'a mut Dllink<(usize, T)>, i32)>,
+        T: 'a,
+    {
+        let mut staged: Vec<_> = items.into_iter().collect();
+        for (_, k) in staged.iter() {
+            self.check_key(*k);
+        }
+        let mut max = self.max;
+        for (it, k) in staged.iter_mut() {
+            it.data.0 = (*k - self.offset) as usize;
+            if max < it.data.0 {
+                max = it.data.0;
+            }
+            self.link(it, order);
+        }
+        self.max = max;
+    }
+
+    /// Append many items, given by vertex id, with external keys at once
+    ///
+    /// Same as [`BPQueue::extend_from`], except that nodes are looked up in
+    /// `nodes` by the vertex ids yielded from `items`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any vertex id is out of bounds for `nodes` or any key lies
+    /// outside the range given to [`BPQueue::new`]. In that case no node has
+    /// been linked.
+    ///
+    /// The vertex ids must be unique: linking the same node twice corrupts
+    /// its bucket. Debug builds check this and panic on a repeated id.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::bpqueue::{BPQueue, InsertOrder};
+    /// use mywheel_rs::dllist::Dllink;
+    ///
+    /// let mut nodes: Vec<_> = (0..4).map(|v| Dllink::new((0, v))).collect();
+    /// let mut bpq = BPQueue::<i32>::new(-3, 3);
+    /// bpq.extend_from_ids(&mut nodes, [(0, -1), (2, 3), (3, 0)], InsertOrder::Stable);
+    ///
+    /// assert_eq!(bpq.get_max(), 3);
+    /// ```
+    pub fn extend_from_ids<I>(
+        &mut self,
+        nodes: &mut [Dllink<(usize, T)>],
+        items: I,
+        order: InsertOrder,
+    ) where
+        I: IntoIterator<Item = (usize, i32)>,
+    {
+        let staged: Vec<_> = items.into_iter().collect();
+        for &(v, k) in staged.iter() {
+            assert!(v < nodes.len(), "vertex id {v} out of bounds");
+            self.check_key(k);
+        }
+        debug_assert!(
+            {
+                let mut seen = vec![false; nodes.len()];
+                staged
+                    .iter()
+                    .all(|&(v, _)| !std::mem::replace(&mut seen[v], true))
+            },
+            "vertex ids must be unique"
+        );
+        let mut max = self.max;
+        for &(v, k) in staged.iter() {
+            let it = &mut nodes[v];
+            it.data.0 = (k - self.offset) as usize;
+            if max < it.data.0 {
+                max = it.data.0;
+            }
+            self.link(it, order);
+        }
+        self.max = max;
+    }
+
+    /// Check that an external key lies inside [a..b]
+    #[inline]
+    fn check_key(&self, k: i32) {
+        assert!(
+            k > self.offset && k - self.offset <= self.high as i32,
+            "key {k} out of range"
+        );
+    }
+
+    /// Link an item with its internal key already set, without touching `max`
+    #[inline]
+    fn link(&mut self, it: &mut Dllink<(usize, T)>, order: InsertOrder) {
+        match order {
+            InsertOrder::Stable => self.bucket[it.data.0].append(it),
+            InsertOrder::Reversed => self.bucket[it.data.0].appendleft(it),
+        }
+    }
+
     /// Append item with external key
     ///
     /// # Examples
@@ -308,6 +469,90 @@ impl<T: Default + Clone> BPQueue<T> {
         }
     }
 
+    /// Merge all items of `other` into this queue
+    ///
+    /// Matching buckets are spliced in O(1) each, so the whole merge takes
+    /// O(K) where K is the key range size. Items of `other` are placed
+    /// behind the items already in the same bucket, and `other` is left empty.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two queues were not constructed with the same range.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::bpqueue::BPQueue;
+    /// use mywheel_rs::dllist::Dllink;
+    ///
+    /// let mut bpq1 = BPQueue::<i32>::new(-3, 3);
+    /// let mut bpq2 = BPQueue::<i32>::new(-3, 3);
+    /// let mut a = Dllink::<(usize, i32)>::new((0, 3));
+    /// bpq2.append(&mut a, 2);
+    /// bpq1.merge_from(&mut bpq2);
+    ///
+    /// assert_eq!(bpq1.get_max(), 2);
+    /// assert!(bpq2.is_empty());
+    /// ```
+    pub fn merge_from(&mut self, other: &mut BPQueue<T>) {
+        assert!(
+            self.offset == other.offset && self.high == other.high,
+            "BPQueue ranges differ"
+        );
+        for k in 1..=other.max {
+            self.bucket[k].splice(&mut other.bucket[k]);
+        }
+        if self.max < other.max {
+            self.max = other.max;
+        }
+        other.max = 0;
+    }
+
+    /// Move the items whose payload satisfies `pred` into a new queue
+    ///
+    /// The new queue has the same range as this one, and items keep their
+    /// keys and their relative order within each bucket. This is the reverse
+    /// of [`BPQueue::merge_from`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::bpqueue::BPQueue;
+    /// use mywheel_rs::dllist::Dllink;
+    ///
+    /// let mut bpq = BPQueue::<i32>::new(-3, 3);
+    /// let mut a = Dllink::<(usize, i32)>::new((0, 3));
+    /// let mut b = Dllink::<(usize, i32)>::new((0, 4));
+    /// bpq.append(&mut a, 2);
+    /// bpq.append(&mut b, -1);
+    /// let odd = bpq.split_where(|v| v % 2 == 1);
+    ///
+    /// assert_eq!(odd.get_max(), 2);
+    /// assert_eq!(bpq.get_max(), -1);
+    /// ```
+    pub fn split_where<F>(&mut self, mut pred: F) -> BPQueue<T>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut res = Self::new(self.offset + 1, self.offset + self.high as i32);
+        for k in 1..=self.max {
+            for it in self.bucket[k].iter_mut() {
+                if pred(&it.data.1) {
+                    it.detach();
+                    res.bucket[k].append(it);
+                }
+            }
+        }
+        res.max = self.max;
+        while self.bucket[self.max].is_empty() {
+            self.max -= 1;
+        }
+        while res.bucket[res.max].is_empty() {
+            res.max -= 1;
+        }
+        res
+    }
+
     /// Decrease key by delta
     ///
     /// Note that the order of items with same key will not be preserved.
@@ -409,6 +654,144 @@ impl<T: Default + Clone> BPQueue<T> {
     }
 }
 
+impl<T: fmt::Display> BPQueue<T> {
+    /// Iterate over the non-empty buckets from the highest key down
+    fn nonempty_buckets(&self) -> impl Iterator<Item = (i32, &Dllist<(usize, T)>)> {
+        (1..=self.max)
+            .rev()
+            .filter(move |&k| self.bucket[k].iter().next().is_some())
+            .map(move |k| (self.offset + k as i32, &self.bucket[k]))
+    }
+
+    /// Return a compact single-line view for log output
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::bpqueue::BPQueue;
+    /// use mywheel_rs::dllist::Dllink;
+    ///
+    /// let mut bpq = BPQueue::<i32>::new(-3, 3);
+    /// let mut a = Dllink::<(usize, i32)>::new((0, 3));
+    /// let mut b = Dllink::<(usize, i32)>::new((0, 4));
+    /// bpq.append(&mut a, 0);
+    /// bpq.append(&mut b, 2);
+    ///
+    /// assert_eq!(bpq.summary().to_string(), "BPQueue[-3..=3] max=2 {2: [4], 0: [3]}");
+    /// ```
+    pub fn summary(&self) -> Summary<'_, T> {
+        Summary { bpq: self }
+    }
+
+    /// Render the buckets and their linked nodes as a Graphviz DOT graph
+    ///
+    /// Only non-empty buckets are drawn. Buckets are stacked from the
+    /// highest key down and drawn in lightblue, and each bucket points to
+    /// its chain of nodes drawn in papayawhip.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::bpqueue::BPQueue;
+    /// use mywheel_rs::dllist::Dllink;
+    ///
+    /// let mut bpq = BPQueue::<i32>::new(-3, 3);
+    /// let mut a = Dllink::<(usize, i32)>::new((0, 3));
+    /// bpq.append(&mut a, -1);
+    /// let dot = bpq.to_dot();
+    ///
+    /// assert!(dot.contains("k3 [label=\"-1\""));
+    /// assert!(dot.contains("k3 -> k3_0;"));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut res = String::from(
+            "digraph BPQueue {\n    rankdir=LR;\n    node [shape=box, style=filled];\n",
+        );
+        let mut names = vec![];
+        for (key, lst) in self.nonempty_buckets() {
+            let name = format!("k{}", key - self.offset);
+            res.push_str(&format!(
+                "    {name} [label=\"{key}\", fillcolor=lightblue];\n"
+            ));
+            let mut prev = name.clone();
+            for (i, node) in lst.iter().enumerate() {
+                let child = format!("{name}_{i}");
+                res.push_str(&format!(
+                    "    {child} [label=\"{}\", fillcolor=papayawhip];\n    {prev} -> {child};\n",
+                    dot_escape(&node.data.1)
+                ));
+                prev = child;
+            }
+            names.push(name);
+        }
+        if names.len() > 1 {
+            res.push_str(&format!("    {{ rank=same; {}; }}\n", names.join("; ")));
+            res.push_str(&format!("    {} [style=invis];\n", names.join(" -> ")));
+        }
+        res.push_str("}\n");
+        res
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for BPQueue<T> {
+    /// Formats each non-empty bucket on its own line as `key: [payloads...]`,
+    /// from the highest key down.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, lst)) in self.nonempty_buckets().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            fmt_bucket(f, key, lst)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write one bucket as `key: [payloads...]`
+fn fmt_bucket<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    key: i32,
+    lst: &Dllist<(usize, T)>,
+) -> fmt::Result {
+    write!(f, "{key}: [")?;
+    for (i, node) in lst.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", node.data.1)?;
+    }
+    write!(f, "]")
+}
+
+/// Compact single-line view of a [`BPQueue`]
+///
+/// Created by [`BPQueue::summary`]. Formats as
+/// `BPQueue[a..=b] max=k {key: [payloads...], ...}`.
+#[derive(Debug)]
+pub struct Summary<'a, T> {
+    bpq: &'a BPQueue<T>,
+}
+
+impl<'a, T: fmt::Display> fmt::Display for Summary<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bpq = self.bpq;
+        write!(
+            f,
+            "BPQueue[{}..={}] max={} {{",
+            bpq.offset + 1,
+            bpq.offset + bpq.high as i32,
+            bpq.offset + bpq.max as i32
+        )?;
+        for (i, (key, lst)) in bpq.nonempty_buckets().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            fmt_bucket(f, key, lst)?;
+        }
+        write!(f, "}}")
+    }
+}
+
 /// BPQueue iterator
 ///
 /// Traverse the list from the first item. Usually it is safe
@@ -568,4 +951,125 @@ mod tests {
         bpq.modify_key(&mut b, 1);
         assert_eq!(bpq.get_max(), -1);
     }
+
+    fn drain(bpq: &mut BPQueue<i32>) -> Vec<(i32, i32)> {
+        let mut res = vec![];
+        while !bpq.is_empty() {
+            let key = bpq.get_max();
+            let d = bpq.popleft();
+            res.push((key, unsafe { (*d).data.1 }));
+        }
+        res
+    }
+
+    #[test]
+    fn test_extend_from() {
+        let mut nodes: Vec<_> = (0..5).map(|v| Dllink::new((0, v))).collect();
+        let keys = [1, -2, 3, 1, -2];
+        let mut bpq = BPQueue::<i32>::new(-3, 3);
+        bpq.extend_from(nodes.iter_mut().zip(keys), InsertOrder::Stable);
+        assert_eq!(bpq.get_max(), 3);
+        assert_eq!(
+            drain(&mut bpq),
+            vec![(3, 2), (1, 0), (1, 3), (-2, 1), (-2, 4)]
+        );
+
+        let mut bpq =
+            BPQueue::from_iter_with_range(-3, 3, nodes.iter_mut().zip(keys), InsertOrder::Reversed);
+        assert_eq!(
+            drain(&mut bpq),
+            vec![(3, 2), (1, 3), (1, 0), (-2, 4), (-2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_extend_from_ids() {
+        let mut nodes: Vec<_> = (0..4).map(|v| Dllink::new((0, v))).collect();
+        let mut bpq = BPQueue::<i32>::new(-3, 3);
+        let mut a = Dllink::new((0, 9));
+        bpq.append(&mut a, 2);
+        bpq.extend_from_ids(&mut nodes, [(3, 0), (1, 2), (0, -3)], InsertOrder::Stable);
+        assert_eq!(bpq.get_max(), 2);
+        assert_eq!(drain(&mut bpq), vec![(2, 9), (2, 1), (0, 3), (-3, 0)]);
+    }
+
+    #[test]
+    fn test_merge_split() {
+        let mut nodes: Vec<_> = (0..6).map(|v| Dllink::new((0, v))).collect();
+        let (left, right) = nodes.split_at_mut(3);
+        let mut bpq1 = BPQueue::from_iter_with_range(
+            -3,
+            3,
+            left.iter_mut().zip([1, -3, 0]),
+            InsertOrder::Stable,
+        );
+        let mut bpq2 = BPQueue::from_iter_with_range(
+            -3,
+            3,
+            right.iter_mut().zip([1, 2, -3]),
+            InsertOrder::Stable,
+        );
+
+        bpq1.merge_from(&mut bpq2);
+        assert!(bpq2.is_empty());
+        assert_eq!(bpq1.get_max(), 2);
+
+        let mut odd = bpq1.split_where(|v| v % 2 == 1);
+        assert_eq!(odd.get_max(), 1);
+        assert_eq!(bpq1.get_max(), 2);
+        assert_eq!(drain(&mut odd), vec![(1, 3), (-3, 1), (-3, 5)]);
+        assert_eq!(drain(&mut bpq1), vec![(2, 4), (1, 0), (0, 2)]);
+
+        let mut none = bpq1.split_where(|_| true);
+        assert!(none.is_empty());
+        none.merge_from(&mut bpq1);
+        assert!(none.is_empty());
+    }
+
+    #[test]
+    fn test_display() {
+        let mut nodes: Vec<_> = (0..4).map(|v| Dllink::new((0, v))).collect();
+        let mut bpq = BPQueue::<i32>::new(-3, 3);
+        assert_eq!(bpq.to_string(), "");
+        assert_eq!(bpq.summary().to_string(), "BPQueue[-3..=3] max=-4 {}");
+
+        bpq.extend_from(nodes.iter_mut().zip([1, -3, 1, 2]), InsertOrder::Stable);
+        assert_eq!(bpq.to_string(), "2: [3]\n1: [0, 2]\n-3: [1]");
+        assert_eq!(
+            bpq.summary().to_string(),
+            "BPQueue[-3..=3] max=2 {2: [3], 1: [0, 2], -3: [1]}"
+        );
+
+        let dot = bpq.to_dot();
+        assert!(dot.starts_with("digraph BPQueue {"));
+        assert!(dot.contains("k5 -> k5_0;\n"));
+        assert!(dot.contains("k5_0 -> k5_1;\n"));
+        assert!(dot.contains("{ rank=same; k6; k5; k1; }"));
+        assert!(dot.contains("k6 -> k5 -> k1 [style=invis];"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_merge_different_ranges() {
+        let mut bpq1 = BPQueue::<i32>::new(-3, 3);
+        let mut bpq2 = BPQueue::<i32>::new(-2, 3);
+        bpq1.merge_from(&mut bpq2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_extend_from_out_of_range() {
+        let mut nodes: Vec<_> = (0..2).map(|v| Dllink::new((0, v))).collect();
+        let mut bpq = BPQueue::<i32>::new(-3, 3);
+        bpq.extend_from_ids(&mut nodes, [(0, 1), (1, 4)], InsertOrder::Stable);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "vertex ids must be unique")]
+    fn test_extend_from_ids_duplicate() {
+        let mut nodes: Vec<_> = (0..2).map(|v| Dllink::new((0, v))).collect();
+        let mut bpq = BPQueue::<i32>::new(-3, 3);
+        bpq.extend_from_ids(&mut nodes, [(1, 1), (0, 2), (1, 3)], InsertOrder::Stable);
+    }
 }
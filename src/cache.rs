@@ -0,0 +1,531 @@
+use crate::bpqueue::BPQueue;
+use crate::dllist::{Dllink, Dllist};
+use crate::map_adapter::MapAdapter;
+
+/// The `LruCache` struct is a least-recently-used cache for dense `usize` keys.
+///
+/// Every key in `0..num_keys` owns a [`Dllink`] slot in a [`MapAdapter`], so
+/// looking a key up is a plain index. Cached keys are linked into a recency
+/// [`Dllist`] with the most recently used key at the front; a slot that is
+/// not cached is locked.
+///
+/// # Performance Characteristics
+///
+/// * **Time Complexity**: O(1) for get, peek, put, evict and remove
+/// * **Space Complexity**: O(num_keys), independent of the capacity
+/// * **Use Cases**: Memoizing recomputations indexed by vertex or net id
+///
+/// # Examples
+///
+/// ```rust
+/// use mywheel_rs::cache::LruCache;
+///
+/// let mut cache = LruCache::new(10, 2);
+/// cache.put(1, "a");
+/// cache.put(2, "b");
+/// assert_eq!(cache.get(1), Some(&"a"));
+/// assert_eq!(cache.put(3, "c"), Some((2, "b")));
+/// assert!(!cache.contains(2));
+/// ```
+#[derive(Debug)]
+pub struct LruCache<V> {
+    nodes: MapAdapter<Dllink<usize>>,
+    values: MapAdapter<Option<V>>,
+    /// Recency list, boxed so that its head does not move with the cache
+    recency: Box<Dllist<usize>>,
+    len: usize,
+    capacity: usize,
+}
+
+impl<V> LruCache<V> {
+    /// Construct a new LruCache for keys in `0..num_keys`
+    ///
+    /// Arguments:
+    ///
+    /// * `num_keys`: The number of distinct keys.
+    /// * `capacity`: The maximum number of cached entries; must be positive.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::cache::LruCache;
+    /// let cache = LruCache::<i32>::new(10, 3);
+    ///
+    /// assert!(cache.is_empty());
+    /// assert_eq!(cache.capacity(), 3);
+    /// ```
+    pub fn new(num_keys: usize, capacity: usize) -> Self {
+        assert!(capacity > 0);
+        let mut nodes = MapAdapter::new((0..num_keys).map(Dllink::new).collect());
+        for node in nodes.lst.iter_mut() {
+            node.lock();
+        }
+        let mut recency = Box::new(Dllist::new(usize::MAX));
+        recency.clear();
+        Self {
+            nodes,
+            values: MapAdapter::new((0..num_keys).map(|_| None).collect()),
+            recency,
+            len: 0,
+            capacity,
+        }
+    }
+
+    /// Returns the number of cached entries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing is cached.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the maximum number of cached entries.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns `true` if `key` is cached.
+    #[inline]
+    pub fn contains(&self, key: usize) -> bool {
+        !self.nodes[key].is_locked()
+    }
+
+    /// Get the cached value of `key` and mark it most recently used
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::cache::LruCache;
+    ///
+    /// let mut cache = LruCache::new(10, 2);
+    /// cache.put(1, 10);
+    /// cache.put(2, 20);
+    /// cache.get(1);
+    /// assert_eq!(cache.evict(), Some((2, 20)));
+    /// ```
+    pub fn get(&mut self, key: usize) -> Option<&V> {
+        if !self.contains(key) {
+            return None;
+        }
+        self.touch(key);
+        self.values[key].as_ref()
+    }
+
+    /// Get the cached value of `key` without changing its recency
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::cache::LruCache;
+    ///
+    /// let mut cache = LruCache::new(10, 2);
+    /// cache.put(1, 10);
+    /// cache.put(2, 20);
+    /// assert_eq!(cache.peek(1), Some(&10));
+    /// assert_eq!(cache.evict(), Some((1, 10)));
+    /// ```
+    pub fn peek(&self, key: usize) -> Option<&V> {
+        self.values[key].as_ref()
+    }
+
+    /// Cache `value` under `key` and mark it most recently used
+    ///
+    /// Returns the displaced entry, if any: the previous value of `key`, or
+    /// the least recently used entry when the cache was full.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::cache::LruCache;
+    ///
+    /// let mut cache = LruCache::new(10, 1);
+    /// assert_eq!(cache.put(1, 10), None);
+    /// assert_eq!(cache.put(1, 11), Some((1, 10)));
+    /// assert_eq!(cache.put(2, 20), Some((1, 11)));
+    /// ```
+    pub fn put(&mut self, key: usize, value: V) -> Option<(usize, V)> {
+        if self.contains(key) {
+            self.touch(key);
+            return self.values[key].replace(value).map(|old| (key, old));
+        }
+        let evicted = if self.len == self.capacity {
+            self.evict()
+        } else {
+            None
+        };
+        self.recency.appendleft(&mut self.nodes[key]);
+        self.values[key] = Some(value);
+        self.len += 1;
+        evicted
+    }
+
+    /// Remove and return the least recently used entry
+    pub fn evict(&mut self) -> Option<(usize, V)> {
+        if self.len == 0 {
+            return None;
+        }
+        // Safety: the list is not empty and its nodes live in `self.nodes`
+        let key = unsafe { (*self.recency.head.prev).data };
+        self.remove(key).map(|value| (key, value))
+    }
+
+    /// Remove `key` from the cache and return its value
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::cache::LruCache;
+    ///
+    /// let mut cache = LruCache::new(10, 2);
+    /// cache.put(1, 10);
+    /// assert_eq!(cache.remove(1), Some(10));
+    /// assert_eq!(cache.remove(1), None);
+    /// ```
+    pub fn remove(&mut self, key: usize) -> Option<V> {
+        if !self.contains(key) {
+            return None;
+        }
+        let node = &mut self.nodes[key];
+        node.detach();
+        node.lock();
+        self.len -= 1;
+        self.values[key].take()
+    }
+
+    /// Move a cached key to the front of the recency list
+    fn touch(&mut self, key: usize) {
+        let node = &mut self.nodes[key];
+        node.detach();
+        self.recency.appendleft(node);
+    }
+}
+
+/// The `LfuCache` struct is a least-frequently-used cache for dense `usize` keys.
+///
+/// Cached keys live in a [`BPQueue`] keyed by the negated access frequency,
+/// so the bucket at the queue's maximum holds the least frequently used keys.
+/// Frequencies saturate at `max_freq`. Within a bucket keys are kept in the
+/// order they were last used, which breaks ties in least-recently-used order.
+///
+/// # Performance Characteristics
+///
+/// * **Time Complexity**: O(1) for get, peek and remove; put and evict are
+///   O(1) amortized over the frequency range
+/// * **Space Complexity**: O(num_keys + max_freq)
+/// * **Use Cases**: Caches whose hot set is stable over time
+///
+/// # Examples
+///
+/// ```rust
+/// use mywheel_rs::cache::LfuCache;
+///
+/// let mut cache = LfuCache::new(10, 2, 16);
+/// cache.put(1, "a");
+/// cache.put(2, "b");
+/// cache.get(1);
+/// cache.get(2);
+/// cache.get(2);
+/// assert_eq!(cache.put(3, "c"), Some((1, "a")));
+/// assert_eq!(cache.frequency(2), Some(3));
+/// ```
+#[derive(Debug)]
+pub struct LfuCache<V> {
+    nodes: MapAdapter<Dllink<(usize, usize)>>,
+    values: MapAdapter<Option<V>>,
+    queue: BPQueue<usize>,
+    len: usize,
+    capacity: usize,
+    max_freq: usize,
+}
+
+impl<V> LfuCache<V> {
+    /// Construct a new LfuCache for keys in `0..num_keys`
+    ///
+    /// Arguments:
+    ///
+    /// * `num_keys`: The number of distinct keys.
+    /// * `capacity`: The maximum number of cached entries; must be positive.
+    /// * `max_freq`: The frequency at which counting saturates; must be positive.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::cache::LfuCache;
+    /// let cache = LfuCache::<i32>::new(10, 3, 8);
+    ///
+    /// assert!(cache.is_empty());
+    /// ```
+    pub fn new(num_keys: usize, capacity: usize, max_freq: usize) -> Self {
+        assert!(capacity > 0);
+        assert!(max_freq > 0 && max_freq < i32::MAX as usize);
+        let mut nodes = MapAdapter::new((0..num_keys).map(|k| Dllink::new((0, k))).collect());
+        for node in nodes.lst.iter_mut() {
+            node.lock();
+        }
+        Self {
+            nodes,
+            values: MapAdapter::new((0..num_keys).map(|_| None).collect()),
+            queue: BPQueue::new(-(max_freq as i32), -1),
+            len: 0,
+            capacity,
+            max_freq,
+        }
+    }
+
+    /// Returns the number of cached entries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing is cached.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the maximum number of cached entries.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns `true` if `key` is cached.
+    #[inline]
+    pub fn contains(&self, key: usize) -> bool {
+        !self.nodes[key].is_locked()
+    }
+
+    /// Returns the (saturated) access frequency of a cached key.
+    pub fn frequency(&self, key: usize) -> Option<usize> {
+        if !self.contains(key) {
+            return None;
+        }
+        // Internal key 1 stands for -max_freq
+        Some(self.max_freq + 1 - self.nodes[key].data.0)
+    }
+
+    /// Get the cached value of `key` and count the access
+    pub fn get(&mut self, key: usize) -> Option<&V> {
+        if !self.contains(key) {
+            return None;
+        }
+        self.touch(key);
+        self.values[key].as_ref()
+    }
+
+    /// Get the cached value of `key` without counting the access
+    pub fn peek(&self, key: usize) -> Option<&V> {
+        self.values[key].as_ref()
+    }
+
+    /// Cache `value` under `key` and count the access
+    ///
+    /// Returns the displaced entry, if any: the previous value of `key`, or
+    /// the least frequently used entry when the cache was full.
+    pub fn put(&mut self, key: usize, value: V) -> Option<(usize, V)> {
+        if self.contains(key) {
+            self.touch(key);
+            return self.values[key].replace(value).map(|old| (key, old));
+        }
+        let evicted = if self.len == self.capacity {
+            self.evict()
+        } else {
+            None
+        };
+        self.queue.append(&mut self.nodes[key], -1);
+        self.values[key] = Some(value);
+        self.len += 1;
+        evicted
+    }
+
+    /// Remove and return the least frequently used entry
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::cache::LfuCache;
+    ///
+    /// let mut cache = LfuCache::new(10, 3, 8);
+    /// cache.put(1, 10);
+    /// cache.put(2, 20);
+    /// cache.get(1);
+    /// assert_eq!(cache.evict(), Some((2, 20)));
+    /// assert_eq!(cache.evict(), Some((1, 10)));
+    /// assert_eq!(cache.evict(), None);
+    /// ```
+    pub fn evict(&mut self) -> Option<(usize, V)> {
+        if self.len == 0 {
+            return None;
+        }
+        let node = self.queue.popleft();
+        // Safety: the queue is not empty and its nodes live in `self.nodes`
+        let key = unsafe {
+            (*node).lock();
+            (*node).data.1
+        };
+        self.len -= 1;
+        self.values[key].take().map(|value| (key, value))
+    }
+
+    /// Remove `key` from the cache and return its value
+    pub fn remove(&mut self, key: usize) -> Option<V> {
+        if !self.contains(key) {
+            return None;
+        }
+        let node = &mut self.nodes[key];
+        self.queue.detach(node);
+        node.lock();
+        self.len -= 1;
+        self.values[key].take()
+    }
+
+    /// Count one access of a cached key
+    fn touch(&mut self, key: usize) {
+        let node = &mut self.nodes[key];
+        if node.data.0 > 1 {
+            self.queue.decrease_key(node, 1);
+        } else {
+            // Saturated: only refresh the tie-breaking order
+            self.queue.detach(node);
+            self.queue.append(node, -(self.max_freq as i32));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    #[test]
+    fn test_lru_cache() {
+        let mut cache = LruCache::new(8, 3);
+        for k in 0..3 {
+            assert_eq!(cache.put(k, k * 10), None);
+        }
+        assert_eq!(cache.get(0), Some(&0));
+        assert_eq!(cache.put(3, 30), Some((1, 10)));
+        assert_eq!(cache.remove(2), Some(20));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.put(4, 40), None);
+        assert_eq!(cache.evict(), Some((0, 0)));
+        assert_eq!(cache.get(7), None);
+    }
+
+    #[test]
+    fn test_lfu_cache_saturation() {
+        let mut cache = LfuCache::new(4, 2, 2);
+        cache.put(0, 'a');
+        cache.put(1, 'b');
+        for _ in 0..5 {
+            cache.get(0);
+        }
+        cache.get(1);
+        assert_eq!(cache.frequency(0), Some(2));
+        assert_eq!(cache.frequency(1), Some(2));
+        // Both saturated: 0 was used less recently
+        assert_eq!(cache.put(2, 'c'), Some((0, 'a')));
+    }
+
+    /// Reference model: a vector of (key, value, freq, last_use)
+    type Model = Vec<(usize, u8, usize, usize)>;
+
+    fn model_victim(model: &Model, lfu: bool) -> usize {
+        (0..model.len())
+            .min_by_key(|&i| {
+                let (_, _, freq, last) = model[i];
+                (if lfu { freq } else { 0 }, last)
+            })
+            .unwrap()
+    }
+
+    fn run_ops(ops: &[(u8, u8, u8)], lfu: bool) -> bool {
+        const NUM_KEYS: usize = 12;
+        const CAPACITY: usize = 4;
+        const MAX_FREQ: usize = 3;
+        let mut lru = LruCache::new(NUM_KEYS, CAPACITY);
+        let mut lfc = LfuCache::new(NUM_KEYS, CAPACITY, MAX_FREQ);
+        let mut model: Model = Vec::new();
+        for (clock, &(op, key, value)) in ops.iter().enumerate() {
+            let key = key as usize % NUM_KEYS;
+            let pos = model.iter().position(|e| e.0 == key);
+            match op % 4 {
+                0 | 1 => {
+                    let got = if lfu {
+                        lfc.get(key).copied()
+                    } else {
+                        lru.get(key).copied()
+                    };
+                    if got != pos.map(|i| model[i].1) {
+                        return false;
+                    }
+                    if let Some(i) = pos {
+                        model[i].2 = (model[i].2 + 1).min(MAX_FREQ);
+                        model[i].3 = clock;
+                    }
+                }
+                2 => {
+                    let got = if lfu {
+                        lfc.put(key, value)
+                    } else {
+                        lru.put(key, value)
+                    };
+                    let expected = if let Some(i) = pos {
+                        let old = model[i].1;
+                        model[i] = (key, value, (model[i].2 + 1).min(MAX_FREQ), clock);
+                        Some((key, old))
+                    } else {
+                        let evicted = if model.len() == CAPACITY {
+                            let e = model.remove(model_victim(&model, lfu));
+                            Some((e.0, e.1))
+                        } else {
+                            None
+                        };
+                        model.push((key, value, 1, clock));
+                        evicted
+                    };
+                    if got != expected {
+                        return false;
+                    }
+                }
+                _ => {
+                    let got = if lfu {
+                        lfc.remove(key)
+                    } else {
+                        lru.remove(key)
+                    };
+                    if got != pos.map(|i| model.remove(i).1) {
+                        return false;
+                    }
+                }
+            }
+            let len = if lfu { lfc.len() } else { lru.len() };
+            if len != model.len() {
+                return false;
+            }
+        }
+        true
+    }
+
+    #[test]
+    fn prop_lru_matches_model() {
+        fn prop(ops: Vec<(u8, u8, u8)>) -> bool {
+            run_ops(&ops, false)
+        }
+        QuickCheck::new().quickcheck(prop as fn(Vec<(u8, u8, u8)>) -> bool);
+    }
+
+    #[test]
+    fn prop_lfu_matches_model() {
+        fn prop(ops: Vec<(u8, u8, u8)>) -> bool {
+            run_ops(&ops, true)
+        }
+        QuickCheck::new().quickcheck(prop as fn(Vec<(u8, u8, u8)>) -> bool);
+    }
+}
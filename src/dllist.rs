@@ -141,6 +141,36 @@ impl<T> Dllink<T> {
         std::ptr::eq(self.next, self)
     }
 
+    /// Whether the node is linked into a list
+    ///
+    /// A node is not linked if it is locked, or if it was built with
+    /// [`Dllink::new`] and never cleared, locked or appended anywhere: such
+    /// a node still has null links. Containers that do not own their nodes,
+    /// e.g. [`TimerWheel`](crate::timer_wheel::TimerWheel), use this to tell
+    /// whether there is anything to remove, and a [`Dllist`] whose head is
+    /// not linked iterates as empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::dllist::{Dllist, Dllink};
+    /// let mut a = Dllist::new(0);
+    /// let mut b = Dllink::new(3);
+    /// assert!(!b.is_linked());
+    ///
+    /// a.clear();
+    /// a.append(&mut b);
+    /// assert!(b.is_linked());
+    ///
+    /// b.detach();
+    /// b.lock();
+    /// assert!(!b.is_linked());
+    /// ```
+    #[inline]
+    pub fn is_linked(&self) -> bool {
+        !self.next.is_null() && !self.is_locked()
+    }
+
     /// Append the node to the front
     ///
     /// # Examples
@@ -449,6 +479,45 @@ impl<T> Dllist<T> {
     }
 }
 
+impl<T> Dllist<T> {
+    /// Move all nodes of `other` to the back of this list
+    ///
+    /// The nodes keep their relative order and `other` is left empty.
+    /// Runs in O(1) regardless of the lengths of both lists.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::dllist::{Dllist, Dllink};
+    /// let mut a = Dllist::new(0);
+    /// let mut b = Dllist::new(0);
+    /// a.clear();
+    /// b.clear();
+    /// let mut c = Dllink::new(3);
+    /// b.append(&mut c);
+    /// a.splice(&mut b);
+    ///
+    /// assert!(!a.is_empty());
+    /// assert!(b.is_empty());
+    /// ```
+    pub fn splice(&mut self, other: &mut Dllist<T>) {
+        if other.is_empty() {
+            return;
+        }
+        let first = other.head.next;
+        let last = other.head.prev;
+        // Safety: first and last are valid nodes of `other`, and self.head.prev
+        // is a valid node of this list. We relink the whole chain behind it.
+        unsafe {
+            (*self.head.prev).next = first;
+            (*first).prev = self.head.prev;
+            (*last).next = &mut self.head as *mut Dllink<T>;
+        }
+        self.head.prev = last;
+        other.clear();
+    }
+}
+
 /// List iterator
 ///
 /// Traverse the list from the first item. Usually it is safe
@@ -509,6 +578,125 @@ impl<'a, T> Iterator for DllIterator<'a, T> {
     }
 }
 
+/// Shared list iterator
+///
+/// Traverse the list from the first item without modifying it.
+///
+/// Properties:
+///
+/// * `curr`: Pointer to the current node.
+/// * `link`: Shared reference to the sentinel (head) node.
+#[derive(Debug)]
+pub struct DllIter<'a, T> {
+    curr: *const Dllink<T>,
+    link: &'a Dllink<T>,
+}
+
+impl<T> Dllist<T> {
+    /// Return a new DllIter object
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::dllist::{Dllist, Dllink};
+    /// let mut a = Dllist::new(0);
+    /// a.clear();
+    /// let mut b = Dllink::new(3);
+    /// a.append(&mut b);
+    ///
+    /// assert_eq!(a.iter().map(|n| n.data).collect::<Vec<_>>(), vec![3]);
+    /// ```
+    ///
+    /// A list that has not been cleared yet iterates as empty.
+    pub fn iter(&self) -> DllIter<'_, T> {
+        let curr = if self.head.is_linked() {
+            self.head.next as *const Dllink<T>
+        } else {
+            &self.head as *const Dllink<T>
+        };
+        DllIter {
+            curr,
+            link: &self.head,
+        }
+    }
+}
+
+impl<'a, T> Iterator for DllIter<'a, T> {
+    type Item = &'a Dllink<T>;
+
+    /// Return a next item
+    fn next(&mut self) -> Option<Self::Item> {
+        if !std::ptr::eq(self.curr, self.link) {
+            let res = self.curr;
+            // Safety: self.curr is a valid pointer to a Dllink<T> node
+            // We're advancing iterator and returning shared reference to current node
+            unsafe {
+                self.curr = (*self.curr).next;
+                return Some(&*res);
+            }
+        }
+        None
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Dllist<T> {
+    /// Formats the payloads from front to back as `[a, b, c]`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+        for (i, node) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", node.data)?;
+        }
+        write!(f, "]")
+    }
+}
+
+impl<T: std::fmt::Display> Dllist<T> {
+    /// Render the list as a Graphviz DOT graph
+    ///
+    /// The head (sentinel) is drawn in lightblue and the nodes in papayawhip,
+    /// following the diagrams in this documentation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::dllist::{Dllist, Dllink};
+    /// let mut a = Dllist::new(0);
+    /// a.clear();
+    /// let mut b = Dllink::new(3);
+    /// a.append(&mut b);
+    ///
+    /// assert!(a.to_dot().contains("n0 [label=\"3\""));
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut res = String::from(
+            "digraph Dllist {\n    rankdir=LR;\n    node [shape=box, style=filled];\n",
+        );
+        res.push_str("    head [label=\"head\", fillcolor=lightblue];\n");
+        let mut prev = String::from("head");
+        for (i, node) in self.iter().enumerate() {
+            let name = format!("n{i}");
+            res.push_str(&format!(
+                "    {name} [label=\"{}\", fillcolor=papayawhip];\n    {prev} -> {name};\n",
+                dot_escape(&node.data)
+            ));
+            prev = name;
+        }
+        if prev != "head" {
+            res.push_str(&format!("    {prev} -> head [style=dashed];\n"));
+        }
+        res.push_str("}\n");
+        res
+    }
+}
+
+/// Escape a label for use inside a double-quoted DOT string
+pub(crate) fn dot_escape<T: std::fmt::Display>(value: &T) -> String {
+    value.to_string().replace('\\', "\\\\").replace('"', "\\\"")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -604,6 +792,56 @@ mod tests {
         assert_eq!(count, 3);
     }
 
+    #[test]
+    fn test_dllist_splice() {
+        let mut l1 = Dllist::new(99);
+        let mut l2 = Dllist::new(99);
+        let mut d = Dllink::new(1);
+        let mut e = Dllink::new(2);
+        let mut f = Dllink::new(3);
+
+        l1.clear();
+        l2.clear();
+        l1.append(&mut d);
+        l2.append(&mut e);
+        l2.append(&mut f);
+        l1.splice(&mut l2);
+        assert!(l2.is_empty());
+        assert_eq!(
+            l1.iter_mut().map(|n| n.data).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+
+        l2.splice(&mut l1);
+        assert!(l1.is_empty());
+        assert_eq!(
+            l2.iter_mut().map(|n| n.data).collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        l2.splice(&mut l1); // splicing an empty list is a no-op
+        assert_eq!(l2.pop(), &mut f as *mut Dllink<i32>);
+    }
+
+    #[test]
+    fn test_dllist_display() {
+        let mut l1 = Dllist::new(99);
+        let mut d = Dllink::new(1);
+        let mut e = Dllink::new(2);
+
+        l1.clear();
+        assert_eq!(l1.to_string(), "[]");
+        assert_eq!(l1.to_dot().matches("->").count(), 0);
+        l1.append(&mut d);
+        l1.append(&mut e);
+        assert_eq!(l1.to_string(), "[1, 2]");
+        let dot = l1.to_dot();
+        assert!(dot.starts_with("digraph Dllist {"));
+        assert!(dot.contains("head -> n0;"));
+        assert!(dot.contains("n0 -> n1;"));
+        assert!(dot.contains("n1 -> head [style=dashed];"));
+        assert_eq!(dot_escape(&"a\"b"), "a\\\"b");
+    }
+
     #[test]
     #[should_panic]
     fn test_pop_from_empty_list() {
@@ -619,4 +857,12 @@ mod tests {
         a.clear();
         a.popleft();
     }
+
+    #[test]
+    fn test_dllist_uncleared_formats_empty() {
+        let l = Dllist::<i32>::new(0);
+        assert_eq!(l.iter().count(), 0);
+        assert_eq!(format!("{}", l), "[]");
+        assert!(!l.to_dot().contains("->"));
+    }
 }
@@ -0,0 +1,377 @@
+use crate::dllist::{Dllink, Dllist};
+use crate::graph::Csr;
+
+/// The `MaxFlow` struct is the result of [`max_flow`].
+///
+/// Properties:
+///
+/// * `value`: The value of a maximum flow, which equals the capacity of a minimum cut.
+/// * `source_side`: `source_side[v]` is `true` if `v` is on the source side of a
+///   minimum cut, i.e. `v` cannot reach the sink in the final residual graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MaxFlow {
+    pub value: u64,
+    pub source_side: Vec<bool>,
+}
+
+impl MaxFlow {
+    /// Returns the ids of the edges with positive capacity that cross the
+    /// minimum cut from the source side to the sink side.
+    ///
+    /// Their capacities add up to `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::flow::max_flow;
+    /// use mywheel_rs::graph::Csr;
+    ///
+    /// let (csr, caps) = Csr::from_weighted_edges(3, &[(0, 1, 5), (1, 2, 2)]);
+    /// let res = max_flow(&csr, &caps, 0, 2);
+    ///
+    /// assert_eq!(res.cut_edges(&csr, &caps), vec![1]);
+    /// ```
+    pub fn cut_edges(&self, csr: &Csr, capacities: &[u64]) -> Vec<usize> {
+        (0..csr.num_nodes())
+            .filter(|&u| self.source_side[u])
+            .flat_map(|u| csr.edges(u))
+            .filter(|&e| capacities[e] > 0 && !self.source_side[csr.indices[e]])
+            .collect()
+    }
+}
+
+#[doc = svgbobdoc::transform!(
+/// Maximum flow and minimum cut with the highest-label push-relabel algorithm.
+///
+/// Active vertices (those holding excess flow) are kept in per-height
+/// buckets of intrusive [`Dllist`]s together with a pointer to the highest
+/// non-empty bucket, exactly like the gain buckets of a `BPQueue`. The
+/// highest active vertex is always discharged first. A second set of
+/// per-height buckets holds every labelled vertex, so that when relabelling
+/// empties a height (a "gap"), all vertices above it are lifted out of
+/// consideration with O(1) bucket operations each.
+///
+/// Only the first phase of push-relabel is run: it computes the flow value
+/// and a minimum cut, but the excess stranded behind a gap is not returned
+/// to the source.
+///
+/// # Performance Characteristics
+///
+/// * **Time Complexity**: O(V² √E)
+/// * **Space Complexity**: O(V + E)
+/// * **Use Cases**: Minimum s-t cuts, net-weighted bipartitioning baselines
+///
+/// ```svgbob
+///  height
+///      +---+    +-----+    +-----+
+///    4 |  -|--->| {c} |--->| {c} |  <-- highest active, discharged next
+///      +---+    +-----+    +-----+
+///    3 |   |
+///      +---+    +-----+
+///    2 |  -|--->| {c} |
+///      +---+    +-----+
+///    1 |   |
+///      +---+
+///
+/// # Legend:
+/// c = {
+///     fill: papayawhip;
+/// }
+/// ```
+///
+/// Arguments:
+///
+/// * `csr`: The directed graph.
+/// * `capacities`: The capacity of every edge, parallel to `csr.indices`.
+/// * `source`: The source vertex.
+/// * `sink`: The sink vertex.
+///
+/// # Panics
+///
+/// Panics if `source` or `sink` is not a vertex, if they are equal, or if
+/// `capacities` does not have one entry per edge.
+///
+/// # Examples
+///
+/// ```rust
+/// use mywheel_rs::flow::max_flow;
+/// use mywheel_rs::graph::Csr;
+///
+/// let (csr, caps) = Csr::from_weighted_edges(
+///     4,
+///     &[(0, 1, 3), (0, 2, 2), (1, 2, 1), (1, 3, 2), (2, 3, 3)],
+/// );
+/// let res = max_flow(&csr, &caps, 0, 3);
+///
+/// assert_eq!(res.value, 5);
+/// assert_eq!(res.source_side, vec![true, true, true, false]);
+/// ```
+)]
+pub fn max_flow(csr: &Csr, capacities: &[u64], source: usize, sink: usize) -> MaxFlow {
+    let n = csr.num_nodes();
+    let m = csr.num_edges();
+    assert!(source < n && sink < n, "max_flow: terminal is not a vertex");
+    assert_ne!(source, sink, "max_flow: source and sink must differ");
+    assert_eq!(capacities.len(), m);
+
+    // Residual graph: arc 2e is edge e, arc 2e+1 is its reverse.
+    let mut to = vec![0; 2 * m];
+    let mut cap = vec![0; 2 * m];
+    let mut res_ptr = vec![0; n + 1];
+    for u in 0..n {
+        for e in csr.edges(u) {
+            let v = csr.indices[e];
+            to[2 * e] = v;
+            to[2 * e + 1] = u;
+            cap[2 * e] = capacities[e];
+            res_ptr[u + 1] += 1;
+            res_ptr[v + 1] += 1;
+        }
+    }
+    for u in 0..n {
+        res_ptr[u + 1] += res_ptr[u];
+    }
+    let mut fill = res_ptr.clone();
+    let mut res_arc = vec![0; 2 * m];
+    for a in 0..2 * m {
+        let tail = to[a ^ 1];
+        res_arc[fill[tail]] = a;
+        fill[tail] += 1;
+    }
+
+    // Exact distance labels to the sink; n means "cannot reach the sink".
+    let mut height = vec![n; n];
+    height[sink] = 0;
+    let mut queue = vec![sink];
+    let mut head = 0;
+    while head < queue.len() {
+        let w = queue[head];
+        head += 1;
+        for &a in &res_arc[res_ptr[w]..res_ptr[w + 1]] {
+            let u = to[a];
+            if cap[a ^ 1] > 0 && height[u] == n && u != source {
+                height[u] = height[w] + 1;
+                queue.push(u);
+            }
+        }
+    }
+    height[source] = n;
+
+    // Buckets indexed by height: `active` holds vertices with excess, `all`
+    // holds every vertex below height n. A locked active node is inactive.
+    let new_buckets = || {
+        let mut res: Vec<Dllist<usize>> = (0..n).map(|_| Dllist::new(n)).collect();
+        for lst in res.iter_mut() {
+            lst.clear();
+        }
+        res
+    };
+    let mut active = new_buckets();
+    let mut all = new_buckets();
+    let mut active_node: Vec<Dllink<usize>> = (0..n).map(Dllink::new).collect();
+    let mut all_node: Vec<Dllink<usize>> = (0..n).map(Dllink::new).collect();
+    let mut max_active = 0;
+    let mut max_all = 0;
+    for v in 0..n {
+        active_node[v].lock();
+        if height[v] < n {
+            all[height[v]].append(&mut all_node[v]);
+            max_all = max_all.max(height[v]);
+        }
+    }
+
+    let mut excess = vec![0; n];
+    for &a in &res_arc[res_ptr[source]..res_ptr[source + 1]] {
+        let v = to[a];
+        if cap[a] == 0 || v == source {
+            continue;
+        }
+        let delta = cap[a];
+        cap[a] = 0;
+        cap[a ^ 1] += delta;
+        if excess[v] == 0 && v != sink && height[v] < n {
+            active[height[v]].append(&mut active_node[v]);
+            max_active = max_active.max(height[v]);
+        }
+        excess[v] += delta;
+    }
+
+    let mut cur = res_ptr.clone();
+    loop {
+        if active[max_active].is_empty() {
+            if max_active == 0 {
+                break;
+            }
+            max_active -= 1;
+            continue;
+        }
+        // Safety: the bucket is not empty, so popleft returns one of `active_node`
+        let v = unsafe { (*active[max_active].popleft()).data };
+        active_node[v].lock();
+
+        // Discharge v
+        while excess[v] > 0 {
+            if cur[v] == res_ptr[v + 1] {
+                let old = height[v];
+                all_node[v].detach();
+                if all[old].is_empty() {
+                    // Gap: nothing above `old` can reach the sink any more.
+                    for lst in all[old + 1..=max_all].iter_mut() {
+                        while !lst.is_empty() {
+                            // Safety: the bucket is not empty
+                            let w = unsafe { (*lst.popleft()).data };
+                            height[w] = n;
+                            if !active_node[w].is_locked() {
+                                active_node[w].detach();
+                                active_node[w].lock();
+                            }
+                        }
+                    }
+                    height[v] = n;
+                    max_all = old - 1;
+                    break;
+                }
+                let new = res_arc[res_ptr[v]..res_ptr[v + 1]]
+                    .iter()
+                    .filter(|&&a| cap[a] > 0)
+                    .map(|&a| height[to[a]] + 1)
+                    .min()
+                    .unwrap_or(n)
+                    .min(n);
+                height[v] = new;
+                cur[v] = res_ptr[v];
+                if new == n {
+                    break;
+                }
+                all[new].append(&mut all_node[v]);
+                max_all = max_all.max(new);
+            } else {
+                let a = res_arc[cur[v]];
+                let w = to[a];
+                if cap[a] > 0 && height[v] == height[w] + 1 {
+                    let delta = excess[v].min(cap[a]);
+                    cap[a] -= delta;
+                    cap[a ^ 1] += delta;
+                    excess[v] -= delta;
+                    if excess[w] == 0 && w != sink {
+                        active[height[w]].append(&mut active_node[w]);
+                        max_active = max_active.max(height[w]);
+                    }
+                    excess[w] += delta;
+                } else {
+                    cur[v] += 1;
+                }
+            }
+        }
+    }
+
+    // Whatever can still reach the sink in the residual graph is on the sink side.
+    let mut sink_side = vec![false; n];
+    sink_side[sink] = true;
+    let mut queue = vec![sink];
+    while let Some(w) = queue.pop() {
+        for &a in &res_arc[res_ptr[w]..res_ptr[w + 1]] {
+            let u = to[a];
+            if cap[a ^ 1] > 0 && !sink_side[u] {
+                sink_side[u] = true;
+                queue.push(u);
+            }
+        }
+    }
+    MaxFlow {
+        value: excess[sink],
+        source_side: sink_side.iter().map(|&t| !t).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    /// Edmonds-Karp on a dense capacity matrix
+    fn reference(n: usize, edges: &[(usize, usize, u64)], s: usize, t: usize) -> u64 {
+        let mut cap = vec![vec![0; n]; n];
+        for &(u, v, c) in edges {
+            cap[u][v] += c;
+        }
+        let mut value = 0;
+        loop {
+            let mut pred = vec![None; n];
+            pred[s] = Some(s);
+            let mut queue = std::collections::VecDeque::from([s]);
+            while let Some(u) = queue.pop_front() {
+                for v in 0..n {
+                    if pred[v].is_none() && cap[u][v] > 0 {
+                        pred[v] = Some(u);
+                        queue.push_back(v);
+                    }
+                }
+            }
+            if pred[t].is_none() {
+                return value;
+            }
+            let mut delta = u64::MAX;
+            let mut v = t;
+            while v != s {
+                let u = pred[v].unwrap();
+                delta = delta.min(cap[u][v]);
+                v = u;
+            }
+            let mut v = t;
+            while v != s {
+                let u = pred[v].unwrap();
+                cap[u][v] -= delta;
+                cap[v][u] += delta;
+                v = u;
+            }
+            value += delta;
+        }
+    }
+
+    #[test]
+    fn test_max_flow_gap() {
+        // Vertex 2 is a dead end, so relabelling it opens a gap.
+        let (csr, caps) =
+            Csr::from_weighted_edges(5, &[(0, 1, 4), (1, 2, 3), (2, 1, 1), (1, 3, 1), (3, 4, 9)]);
+        let res = max_flow(&csr, &caps, 0, 4);
+        assert_eq!(res.value, 1);
+        assert_eq!(res.source_side, vec![true, true, true, false, false]);
+        assert_eq!(res.cut_edges(&csr, &caps), vec![2]); // edge 1 -> 3
+    }
+
+    #[test]
+    fn test_max_flow_disconnected() {
+        let (csr, caps) = Csr::from_weighted_edges(4, &[(0, 1, 4), (2, 3, 4), (0, 0, 7)]);
+        let res = max_flow(&csr, &caps, 0, 3);
+        assert_eq!(res.value, 0);
+        assert_eq!(res.source_side, vec![true, true, false, false]);
+        assert!(res.cut_edges(&csr, &caps).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_max_flow_same_terminals() {
+        let csr = Csr::from_edges(2, &[(0, 1)]);
+        max_flow(&csr, &[1], 1, 1);
+    }
+
+    #[test]
+    fn prop_max_flow_matches_reference() {
+        fn prop(edges: Vec<(u8, u8, u8)>) -> bool {
+            let n = 10;
+            let edges: Vec<_> = edges
+                .iter()
+                .map(|&(u, v, c)| (u as usize % n, v as usize % n, c as u64 % 16))
+                .collect();
+            let (csr, caps) = Csr::from_weighted_edges(n, &edges);
+            let res = max_flow(&csr, &caps, 0, n - 1);
+            let cut: u64 = res.cut_edges(&csr, &caps).iter().map(|&e| caps[e]).sum();
+            res.source_side[0]
+                && !res.source_side[n - 1]
+                && cut == res.value
+                && res.value == reference(n, &edges, 0, n - 1)
+        }
+        QuickCheck::new().quickcheck(prop as fn(Vec<(u8, u8, u8)>) -> bool);
+    }
+}
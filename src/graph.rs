@@ -0,0 +1,339 @@
+use crate::bpqueue::{BPQueue, InsertOrder};
+use crate::dllist::Dllink;
+
+/// The `Csr` struct is a compressed sparse row (CSR) adjacency structure.
+///
+/// The neighbours of vertex `u` are `indices[indptr[u]..indptr[u + 1]]`.
+/// Edge data such as weights or capacities is kept by the caller in a slice
+/// parallel to `indices`, so that edge `e` owns `data[e]`.
+///
+/// # Performance Characteristics
+///
+/// * **Time Complexity**: O(1) to reach the neighbour slice of a vertex
+/// * **Space Complexity**: O(V + E)
+/// * **Cache Performance**: Excellent - neighbours of a vertex are contiguous
+/// * **Use Cases**: Static graphs fed to bucket-queue based algorithms
+///
+/// Properties:
+///
+/// * `indptr`: Offsets into `indices`; has one entry per vertex plus a final one.
+/// * `indices`: The head vertex of every edge, grouped by tail vertex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Csr {
+    pub indptr: Vec<usize>,
+    pub indices: Vec<usize>,
+}
+
+impl Csr {
+    /// Construct a new Csr object from raw arrays
+    ///
+    /// # Panics
+    ///
+    /// Panics if `indptr` is empty, not non-decreasing, does not end at
+    /// `indices.len()`, or if any index is not a valid vertex.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::graph::Csr;
+    /// let csr = Csr::new(vec![0, 2, 3, 3], vec![1, 2, 2]);
+    ///
+    /// assert_eq!(csr.num_nodes(), 3);
+    /// assert_eq!(csr.neighbors(0), &[1, 2]);
+    /// ```
+    pub fn new(indptr: Vec<usize>, indices: Vec<usize>) -> Self {
+        assert!(!indptr.is_empty(), "Csr::new: indptr must not be empty");
+        assert!(
+            indptr[0] == 0 && indptr.windows(2).all(|w| w[0] <= w[1]),
+            "Csr::new: indptr must start at 0 and be non-decreasing"
+        );
+        assert_eq!(indptr[indptr.len() - 1], indices.len());
+        let n = indptr.len() - 1;
+        assert!(
+            indices.iter().all(|&v| v < n),
+            "Csr::new: vertex index out of range"
+        );
+        Self { indptr, indices }
+    }
+
+    /// Construct a new Csr object from a list of directed edges
+    ///
+    /// Edges keep their input order within each tail vertex.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::graph::Csr;
+    /// let csr = Csr::from_edges(3, &[(1, 2), (0, 2), (0, 1)]);
+    ///
+    /// assert_eq!(csr.neighbors(0), &[2, 1]);
+    /// assert_eq!(csr.neighbors(1), &[2]);
+    /// ```
+    pub fn from_edges(num_nodes: usize, edges: &[(usize, usize)]) -> Self {
+        let mut indptr = vec![0; num_nodes + 1];
+        for &(u, _) in edges {
+            indptr[u + 1] += 1;
+        }
+        for u in 0..num_nodes {
+            indptr[u + 1] += indptr[u];
+        }
+        let mut fill = indptr.clone();
+        let mut indices = vec![0; edges.len()];
+        for &(u, v) in edges {
+            indices[fill[u]] = v;
+            fill[u] += 1;
+        }
+        Self::new(indptr, indices)
+    }
+
+    /// Construct a new Csr object from directed edges carrying data
+    ///
+    /// Returns the graph together with the edge data permuted to match the
+    /// CSR edge order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::graph::Csr;
+    /// let (csr, weights) = Csr::from_weighted_edges(3, &[(1, 2, 7), (0, 1, 5)]);
+    ///
+    /// assert_eq!(csr.indices, vec![1, 2]);
+    /// assert_eq!(weights, vec![5, 7]);
+    /// ```
+    pub fn from_weighted_edges<W: Copy>(
+        num_nodes: usize,
+        edges: &[(usize, usize, W)],
+    ) -> (Self, Vec<W>) {
+        let pattern: Vec<_> = edges.iter().map(|&(u, v, _)| (u, v)).collect();
+        let csr = Self::from_edges(num_nodes, &pattern);
+        let mut fill = csr.indptr.clone();
+        let mut data: Vec<Option<W>> = vec![None; edges.len()];
+        for &(u, _, w) in edges {
+            data[fill[u]] = Some(w);
+            fill[u] += 1;
+        }
+        (csr, data.into_iter().flatten().collect())
+    }
+
+    /// Returns the number of vertices.
+    #[inline]
+    pub fn num_nodes(&self) -> usize {
+        self.indptr.len() - 1
+    }
+
+    /// Returns the number of (directed) edges.
+    #[inline]
+    pub fn num_edges(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns the range of edge ids leaving `u`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::graph::Csr;
+    /// let csr = Csr::new(vec![0, 2, 3, 3], vec![1, 2, 2]);
+    ///
+    /// assert_eq!(csr.edges(1), 2..3);
+    /// ```
+    #[inline]
+    pub fn edges(&self, u: usize) -> std::ops::Range<usize> {
+        self.indptr[u]..self.indptr[u + 1]
+    }
+
+    /// Returns the neighbours of `u`.
+    #[inline]
+    pub fn neighbors(&self, u: usize) -> &[usize] {
+        &self.indices[self.edges(u)]
+    }
+
+    /// Returns the number of edges leaving `u`.
+    #[inline]
+    pub fn degree(&self, u: usize) -> usize {
+        self.indptr[u + 1] - self.indptr[u]
+    }
+}
+
+/// Compute the k-core decomposition and a degeneracy ordering of a graph.
+///
+/// Vertices are peeled off one at a time, always taking a vertex of minimum
+/// remaining degree and then decrementing the degree of its neighbours. The
+/// remaining degrees live in a [`BPQueue`] keyed by the negated degree, so
+/// the queue's max is the minimum degree and each decrement is an O(1)
+/// `increase_key`.
+///
+/// The graph is treated as undirected, so `csr` should be symmetric: every
+/// edge `(u, v)` must be accompanied by `(v, u)`.
+///
+/// # Performance Characteristics
+///
+/// * **Time Complexity**: O(V + E + Δ) where Δ is the maximum degree
+/// * **Space Complexity**: O(V + Δ)
+///
+/// Returns:
+///
+/// A pair `(core_numbers, degeneracy_order)`. `core_numbers[v]` is the
+/// largest `k` such that `v` belongs to the k-core, and `degeneracy_order`
+/// lists the vertices in the order they were peeled off.
+///
+/// # Examples
+///
+/// ```rust
+/// use mywheel_rs::graph::{core_decomposition, Csr};
+///
+/// // A triangle 0-1-2 with a pendant vertex 3 attached to 2
+/// let csr = Csr::from_edges(4, &[(0, 1), (1, 0), (1, 2), (2, 1), (0, 2), (2, 0), (2, 3), (3, 2)]);
+/// let (core, order) = core_decomposition(&csr);
+///
+/// assert_eq!(core, vec![2, 2, 2, 1]);
+/// assert_eq!(order[0], 3);
+/// ```
+pub fn core_decomposition(csr: &Csr) -> (Vec<usize>, Vec<usize>) {
+    let n = csr.num_nodes();
+    let mut degree: Vec<usize> = (0..n).map(|u| csr.degree(u)).collect();
+    let max_degree = degree.iter().copied().max().unwrap_or(0);
+
+    let mut bpq = BPQueue::<usize>::new(-(max_degree as i32), 0);
+    let mut nodes: Vec<Dllink<(usize, usize)>> = (0..n).map(|v| Dllink::new((0, v))).collect();
+    bpq.extend_from_ids(
+        &mut nodes,
+        (0..n).map(|v| (v, -(degree[v] as i32))),
+        InsertOrder::Stable,
+    );
+
+    let mut core = vec![0; n];
+    let mut order = Vec::with_capacity(n);
+    let mut k = 0;
+    while !bpq.is_empty() {
+        // Safety: the queue is not empty, so popleft returns one of `nodes`
+        let v = unsafe { (*bpq.popleft()).data.1 };
+        nodes[v].lock(); // peeled off
+        k = k.max(degree[v]);
+        core[v] = k;
+        order.push(v);
+        for &u in csr.neighbors(v) {
+            if !nodes[u].is_locked() {
+                degree[u] -= 1;
+                bpq.increase_key(&mut nodes[u], 1);
+            }
+        }
+    }
+    (core, order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    fn undirected(n: usize, edges: &[(usize, usize)]) -> Csr {
+        let both: Vec<_> = edges.iter().flat_map(|&(u, v)| [(u, v), (v, u)]).collect();
+        Csr::from_edges(n, &both)
+    }
+
+    #[test]
+    fn test_csr_from_edges() {
+        let csr = Csr::from_edges(4, &[(2, 0), (0, 1), (2, 3), (0, 3)]);
+        assert_eq!(csr.indptr, vec![0, 2, 2, 4, 4]);
+        assert_eq!(csr.indices, vec![1, 3, 0, 3]);
+        assert_eq!(csr.num_nodes(), 4);
+        assert_eq!(csr.num_edges(), 4);
+        assert_eq!(csr.degree(1), 0);
+        assert_eq!(csr.neighbors(2), &[0, 3]);
+    }
+
+    #[test]
+    fn test_csr_from_weighted_edges() {
+        let (csr, w) = Csr::from_weighted_edges(3, &[(2, 0, 'a'), (0, 1, 'b'), (2, 1, 'c')]);
+        assert_eq!(csr.indices, vec![1, 0, 1]);
+        assert_eq!(w, vec!['b', 'a', 'c']);
+    }
+
+    #[test]
+    fn test_core_decomposition() {
+        // K4 on {0, 1, 2, 3}, a path 3-4-5 and an isolated vertex 6
+        let csr = undirected(
+            7,
+            &[
+                (0, 1),
+                (0, 2),
+                (0, 3),
+                (1, 2),
+                (1, 3),
+                (2, 3),
+                (3, 4),
+                (4, 5),
+            ],
+        );
+        let (core, order) = core_decomposition(&csr);
+        assert_eq!(core, vec![3, 3, 3, 3, 1, 1, 0]);
+        assert_eq!(order[0], 6);
+        assert_eq!(order.len(), 7);
+
+        let (core, order) = core_decomposition(&Csr::from_edges(0, &[]));
+        assert!(core.is_empty() && order.is_empty());
+    }
+
+    #[test]
+    fn prop_core_decomposition() {
+        fn prop(edges: Vec<(u8, u8)>) -> bool {
+            let n = 12;
+            let edges: Vec<_> = edges
+                .iter()
+                .map(|&(u, v)| (u as usize % n, v as usize % n))
+                .filter(|&(u, v)| u != v)
+                .collect();
+            let csr = undirected(n, &edges);
+            let (core, order) = core_decomposition(&csr);
+
+            // Reference: the k-core is what survives repeatedly deleting
+            // vertices of degree < k.
+            let reference: Vec<usize> = (0..n)
+                .map(|v| {
+                    let mut k = 0;
+                    loop {
+                        let mut alive = vec![true; n];
+                        let mut changed = true;
+                        while changed {
+                            changed = false;
+                            for u in 0..n {
+                                let d = csr.neighbors(u).iter().filter(|&&w| alive[w]).count();
+                                if alive[u] && d <= k {
+                                    alive[u] = false;
+                                    changed = true;
+                                }
+                            }
+                        }
+                        if !alive[v] {
+                            return k;
+                        }
+                        k += 1;
+                    }
+                })
+                .collect();
+
+            // Every vertex has at most `degeneracy` neighbours peeled after it.
+            let degeneracy = core.iter().copied().max().unwrap_or(0);
+            let mut position = vec![0; n];
+            for (i, &v) in order.iter().enumerate() {
+                position[v] = i;
+            }
+            let order_ok = (0..n).all(|v| {
+                csr.neighbors(v)
+                    .iter()
+                    .filter(|&&u| position[u] > position[v])
+                    .count()
+                    <= degeneracy
+            });
+            core == reference && order_ok
+        }
+        QuickCheck::new().quickcheck(prop as fn(Vec<(u8, u8)>) -> bool);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_csr_bad_index() {
+        Csr::new(vec![0, 1], vec![1]);
+    }
+}
@@ -1,13 +1,31 @@
-/// Array-like data structures: RepeatArray and ShiftArray
+/// Algorithms built on the bucket queue machinery
+pub mod algorithms;
+/// Array-like data structures: RepeatArray, ShiftArray, RingArray and ShiftGrid
 pub mod array_like;
 /// Bounded Priority Queue implementation
 pub mod bpqueue;
+/// LRU and LFU caches for dense integer keys
+pub mod cache;
 /// Doubly linked list and node implementations
 pub mod dllist;
+/// Max-flow and min-cut with highest-label push-relabel
+pub mod flow;
+/// Compressed sparse row graphs and k-core decomposition
+pub mod graph;
 /// Map adapter for vector-like access
 pub mod map_adapter;
 /// Round robin scheduling
 pub mod robin;
+/// Fair multi-producer task scheduler on Robin and BPQueue
+pub mod scheduler;
+/// Shortest paths with Dial's bucket-based algorithm
+pub mod shortest_path;
+/// Stable bucket sort and argsort over bounded integer keys
+pub mod sort;
+/// Fill-reducing orderings for sparse matrices
+pub mod sparse;
+/// Hierarchical timing wheel built on Dllist slots
+pub mod timer_wheel;
 
 #[cfg(feature = "std")]
 /// Logging utilities (requires std feature)
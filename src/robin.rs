@@ -1,3 +1,41 @@
+/// Integer types usable as part indices of a [`Robin`] cycle.
+///
+/// Narrow types keep the `next` table small; `u8` supports up to 256 parts
+/// (indices `0..=255`).
+pub trait PartIndex: Copy + Eq + std::fmt::Debug {
+    /// The largest number of parts representable with this index type, i.e.
+    /// one more than the largest index.
+    const MAX_PARTS: usize;
+
+    /// Convert from `usize`; the value must be below `MAX_PARTS`.
+    fn from_usize(i: usize) -> Self;
+
+    /// Convert to `usize`.
+    fn to_usize(self) -> usize;
+}
+
+macro_rules! impl_part_index {
+    ($($t:ty),*) => {
+        $(
+            impl PartIndex for $t {
+                const MAX_PARTS: usize = (<$t>::MAX as usize).saturating_add(1);
+
+                #[inline]
+                fn from_usize(i: usize) -> Self {
+                    i as $t
+                }
+
+                #[inline]
+                fn to_usize(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+
+impl_part_index!(u8, u16, u32, usize);
+
 /// Round-robin scheduler using a circular singly-linked list.
 ///
 /// The cycle is stored as a "next-index" array where `next[i]` is the index of
@@ -6,6 +44,10 @@
 /// `exclude(from_part)` returns an iterator that visits every node **except**
 /// the excluded one, starting from the node immediately after `from_part`.
 ///
+/// The index type `I` (see [`PartIndex`]) defaults to `u8`, which keeps the
+/// table small but limits the cycle to 256 parts (255 through [`Robin::new`]).
+/// Use a wider type through [`Robin::with_num_parts`] for more parts.
+///
 /// # Example
 ///
 /// ```rust
@@ -14,6 +56,9 @@
 /// let rr = Robin::new(6);
 /// let visited: Vec<u8> = rr.exclude(2).collect();
 /// assert_eq!(visited, vec![3, 4, 5, 0, 1]);
+///
+/// let wide = Robin::<u16>::with_num_parts(1024);
+/// assert_eq!(wide.exclude(1023).next(), Some(0));
 /// ```
 ///
 /// ```svgbob
@@ -41,22 +86,46 @@
 ///       └──────────────────────────┘
 /// ```
 #[derive(Debug, Clone)]
-pub struct Robin {
+pub struct Robin<I = u8> {
     /// `next[i]` stores the index of the node that follows node `i` in the cycle.
-    next: Vec<u8>,
+    next: Vec<I>,
+    /// `prev[i]` stores the index of the node that precedes node `i` in the cycle.
+    prev: Vec<I>,
+    /// Whether each node is currently part of the cycle.
+    active: Vec<bool>,
+    /// Number of nodes currently part of the cycle.
+    active_count: usize,
+    /// Some node of the cycle, used when stale links lead nowhere.
+    anchor: I,
+}
+
+/// Stateful position in a [`Robin`] cycle that resumes across calls.
+///
+/// Where [`Robin::exclude`] always restarts right after the excluded part,
+/// a cursor remembers the last part it handed out and continues from
+/// there, so no part is favoured by where the previous round ended.
+///
+/// The cursor holds only its position and borrows the [`Robin`] per call,
+/// so it can be stored next to the cycle and parts can be removed or
+/// inserted between rounds. Created by [`Robin::cursor`] or
+/// [`RobinCursor::new`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RobinCursor<I = u8> {
+    /// The last part handed out, or `None` to start at part 0.
+    last: Option<I>,
 }
 
 /// Iterator over a [`Robin`] cycle that visits every node except one.
 ///
 /// Created by [`Robin::exclude`].
 #[derive(Debug)]
-pub struct RobinIterator<'a> {
+pub struct RobinIterator<'a, I = u8> {
     /// The "next-index" array of the parent [`Robin`].
-    next: &'a [u8],
+    next: &'a [I],
     /// Current position (index into `next`).
-    curr: u8,
-    /// Index of the excluded node – iteration stops when we reach this.
-    stop: u8,
+    curr: I,
+    /// Number of nodes left to visit.
+    remaining: usize,
 }
 
 impl Robin {
@@ -70,48 +139,877 @@ impl Robin {
     /// Panics if `num_parts < 2`.
     #[inline]
     pub fn new(num_parts: u8) -> Robin {
-        let n = num_parts as usize;
+        Robin::with_num_parts(num_parts as usize)
+    }
+
+    /// Construct a round-robin cycle over `num_parts` nodes in a random order.
+    ///
+    /// The cyclic order is a permutation drawn from a small built-in
+    /// generator seeded with `seed`, so the same seed always gives the same
+    /// cycle. `exclude` still visits every other part exactly once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_parts < 2`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mywheel_rs::robin::Robin;
+    ///
+    /// let a = Robin::shuffled(6, 42);
+    /// let b = Robin::shuffled(6, 42);
+    /// assert!(a.exclude(0).eq(b.exclude(0)));
+    ///
+    /// let mut visited: Vec<u8> = a.exclude(0).collect();
+    /// visited.sort();
+    /// assert_eq!(visited, vec![1, 2, 3, 4, 5]);
+    /// ```
+    pub fn shuffled(num_parts: u8, seed: u64) -> Robin {
+        let mut rr = Robin::new(num_parts);
+        rr.reshuffle(seed);
+        rr
+    }
+
+    /// Construct a smooth weighted round-robin over `weights.len()` parts.
+    ///
+    /// Shorthand for [`WeightedRobin::new`] with `u8` part indices.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mywheel_rs::robin::Robin;
+    ///
+    /// let wrr = Robin::with_weights(&[5, 1, 1]);
+    /// assert_eq!(wrr.period().collect::<Vec<_>>(), vec![0, 0, 1, 0, 2, 0, 0]);
+    /// ```
+    #[inline]
+    pub fn with_weights(weights: &[u32]) -> WeightedRobin {
+        WeightedRobin::new(weights)
+    }
+}
+
+impl<I: PartIndex> Robin<I> {
+    /// Construct a round-robin cycle with `num_parts` nodes indexed by `I`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_parts < 2` or `num_parts` exceeds [`PartIndex::MAX_PARTS`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mywheel_rs::robin::Robin;
+    ///
+    /// let rr = Robin::<u32>::with_num_parts(512);
+    /// assert_eq!(rr.num_parts(), 512);
+    /// assert_eq!(rr.exclude(300).take(2).collect::<Vec<_>>(), vec![301, 302]);
+    /// ```
+    pub fn with_num_parts(num_parts: usize) -> Self {
         assert!(
-            n >= 2,
+            num_parts >= 2,
             "Robin::new: num_parts must be at least 2, got {num_parts}"
         );
-        let next: Vec<u8> = (0..n).map(|i| ((i + 1) % n) as u8).collect();
-        Robin { next }
+        assert!(
+            num_parts <= I::MAX_PARTS,
+            "Robin::new: num_parts {num_parts} does not fit the index type"
+        );
+        let next: Vec<I> = (0..num_parts)
+            .map(|i| I::from_usize((i + 1) % num_parts))
+            .collect();
+        let prev: Vec<I> = (0..num_parts)
+            .map(|i| I::from_usize((i + num_parts - 1) % num_parts))
+            .collect();
+        Robin {
+            next,
+            prev,
+            active: vec![true; num_parts],
+            active_count: num_parts,
+            anchor: I::from_usize(0),
+        }
+    }
+
+    /// Rebuild the cycle over the active parts in a random order.
+    ///
+    /// The order depends only on `seed` and on which parts are active, not
+    /// on the current order. Removed parts stay out of the cycle and are
+    /// re-inserted after the first part of the new order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mywheel_rs::robin::Robin;
+    ///
+    /// let mut rr = Robin::<u16>::with_num_parts(300);
+    /// rr.remove(7);
+    /// rr.reshuffle(1);
+    /// assert_eq!(rr.exclude(0).count(), 298);
+    /// assert!(rr.exclude(0).all(|p| p != 7));
+    /// ```
+    pub fn reshuffle(&mut self, seed: u64) {
+        let mut order: Vec<usize> = (0..self.num_parts()).filter(|&i| self.active[i]).collect();
+        if order.is_empty() {
+            return;
+        }
+        let mut rng = SplitMix64::new(seed);
+        for i in (1..order.len()).rev() {
+            order.swap(i, rng.below(i as u64 + 1) as usize);
+        }
+        let k = order.len();
+        for (j, &i) in order.iter().enumerate() {
+            self.next[i] = I::from_usize(order[(j + 1) % k]);
+            self.prev[i] = I::from_usize(order[(j + k - 1) % k]);
+        }
+        // Stale links of removed parts must lead straight into the new cycle
+        let first = I::from_usize(order[0]);
+        for i in 0..self.num_parts() {
+            if !self.active[i] {
+                self.next[i] = first;
+                self.prev[i] = first;
+            }
+        }
+        self.anchor = first;
+    }
+
+    /// Return the number of nodes, active or not.
+    #[inline]
+    pub fn num_parts(&self) -> usize {
+        self.next.len()
+    }
+
+    /// Return the number of nodes currently in the cycle.
+    #[inline]
+    pub fn active_count(&self) -> usize {
+        self.active_count
+    }
+
+    /// Return whether `part` is currently in the cycle.
+    #[inline]
+    pub fn is_active(&self, part: I) -> bool {
+        self.active[part.to_usize()]
+    }
+
+    /// Take `part` out of the cycle in O(1).
+    ///
+    /// The removed node keeps its stale links, which [`Robin::insert`] uses
+    /// to put it back at its old place. Returns `false` if `part` was not
+    /// in the cycle.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mywheel_rs::robin::Robin;
+    ///
+    /// let mut rr = Robin::new(5);
+    /// assert!(rr.remove(3));
+    /// assert!(!rr.remove(3));
+    /// assert_eq!(rr.active_count(), 4);
+    /// assert_eq!(rr.exclude(2).collect::<Vec<_>>(), vec![4, 0, 1]);
+    /// ```
+    pub fn remove(&mut self, part: I) -> bool {
+        let i = part.to_usize();
+        if !self.active[i] {
+            return false;
+        }
+        let (p, n) = (self.prev[i], self.next[i]);
+        self.next[p.to_usize()] = n;
+        self.prev[n.to_usize()] = p;
+        self.active[i] = false;
+        self.active_count -= 1;
+        if self.anchor == part {
+            self.anchor = n;
+        }
+        true
+    }
+
+    /// Put `part` back into the cycle in O(1).
+    ///
+    /// The part is re-linked after its old predecessor if that is still in
+    /// the cycle, else before its old successor, else before the anchor.
+    /// Parts re-inserted in the reverse order of their removal therefore
+    /// regain their old places in the cycle; otherwise a part whose old
+    /// neighbours are both gone may land elsewhere. Returns `false` if
+    /// `part` was already in the cycle.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mywheel_rs::robin::Robin;
+    ///
+    /// let mut rr = Robin::new(5);
+    /// rr.remove(1);
+    /// rr.remove(2);
+    /// assert!(rr.insert(2));
+    /// assert_eq!(rr.exclude(4).collect::<Vec<_>>(), vec![0, 2, 3]);
+    /// assert!(rr.insert(1));
+    /// assert_eq!(rr.exclude(4).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+    /// ```
+    pub fn insert(&mut self, part: I) -> bool {
+        let i = part.to_usize();
+        if self.active[i] {
+            return false;
+        }
+        if self.active_count == 0 {
+            self.next[i] = part;
+            self.prev[i] = part;
+            self.anchor = part;
+        } else {
+            let (old_p, old_n) = (self.prev[i], self.next[i]);
+            let p = if self.is_active(old_p) {
+                old_p
+            } else if self.is_active(old_n) {
+                self.prev[old_n.to_usize()]
+            } else {
+                self.prev[self.anchor.to_usize()]
+            };
+            let n = self.next[p.to_usize()];
+            self.next[p.to_usize()] = part;
+            self.prev[i] = p;
+            self.next[i] = n;
+            self.prev[n.to_usize()] = part;
+        }
+        self.active[i] = true;
+        self.active_count += 1;
+        true
+    }
+
+    /// Follow stale `links` from the inactive `part` to the first active node.
+    ///
+    /// Each link was active when its node was removed, so the walk moves to
+    /// nodes removed later and ends, either at an active node or at a node
+    /// that was removed last and links to itself; the anchor covers the latter.
+    /// Requires at least one active node.
+    fn first_active(&self, links: &[I], part: I) -> I {
+        let mut p = links[part.to_usize()];
+        while !self.active[p.to_usize()] {
+            let q = links[p.to_usize()];
+            if q == p {
+                return self.anchor;
+            }
+            p = q;
+        }
+        p
+    }
+
+    /// Return `part` if it is active, else the first active node after its old place.
+    ///
+    /// Requires at least one active node.
+    #[inline]
+    fn at_or_after(&self, part: I) -> I {
+        if self.is_active(part) {
+            part
+        } else {
+            self.first_active(&self.next, part)
+        }
+    }
+
+    /// Return a round-robin tournament over the active parts.
+    ///
+    /// Parts are seated in cycle order starting from part 0 (or the first
+    /// active part after it). See [`Tournament`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mywheel_rs::robin::Robin;
+    ///
+    /// let mut rr = Robin::new(5);
+    /// rr.remove(4);
+    /// let rounds: Vec<_> = rr.tournament().collect();
+    /// assert_eq!(rounds, vec![vec![(0, 3), (1, 2)], vec![(0, 2), (1, 3)], vec![(0, 1), (2, 3)]]);
+    /// ```
+    pub fn tournament(&self) -> Tournament<I> {
+        let parts = if self.active_count == 0 {
+            Vec::new()
+        } else {
+            let first = self.at_or_after(I::from_usize(0));
+            std::iter::once(first).chain(self.exclude(first)).collect()
+        };
+        Tournament::from_parts(parts)
+    }
+
+    /// Return a cursor positioned before part 0.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mywheel_rs::robin::Robin;
+    ///
+    /// let rr = Robin::new(4);
+    /// let mut cursor = rr.cursor();
+    /// assert_eq!(cursor.next_excluding(&rr, 3), Some(0));
+    /// assert_eq!(cursor.next_excluding(&rr, 3), Some(1));
+    /// // A new round continues after 1 instead of restarting after 3
+    /// assert_eq!(cursor.next_excluding(&rr, 3), Some(2));
+    /// assert_eq!(cursor.next_excluding(&rr, 3), Some(0));
+    /// ```
+    #[inline]
+    pub fn cursor(&self) -> RobinCursor<I> {
+        RobinCursor::new()
+    }
+
+    /// Return an iterator over the nodes after `from_part` that satisfy `pred`.
+    ///
+    /// Visits the same nodes in the same order as [`Robin::exclude`], without
+    /// allocating, and yields those for which `pred` returns `true`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mywheel_rs::robin::Robin;
+    ///
+    /// let rr = Robin::new(6);
+    /// let evens: Vec<u8> = rr.filter_iter(3, |p| p % 2 == 0).collect();
+    /// assert_eq!(evens, vec![4, 0, 2]);
+    /// ```
+    #[inline]
+    pub fn filter_iter<'a, F>(&'a self, from_part: I, mut pred: F) -> impl Iterator<Item = I> + 'a
+    where
+        F: FnMut(I) -> bool + 'a,
+    {
+        self.exclude(from_part).filter(move |&p| pred(p))
+    }
+
+    /// Return an iterator over the nodes after `from_part` that are not in `skip`.
+    ///
+    /// Each visited node costs one bit test, so skipping stays O(1) per node.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mywheel_rs::robin::{PartSet, Robin};
+    ///
+    /// let rr = Robin::new(6);
+    /// let mut full = PartSet::new(6);
+    /// full.insert(0);
+    /// full.insert(4);
+    /// assert_eq!(rr.exclude_set(3, &full).collect::<Vec<_>>(), vec![5, 1, 2]);
+    /// ```
+    #[inline]
+    pub fn exclude_set<'a>(
+        &'a self,
+        from_part: I,
+        skip: &'a PartSet,
+    ) -> impl Iterator<Item = I> + 'a {
+        self.exclude(from_part)
+            .filter(move |p| !skip.contains(p.to_usize()))
+    }
+
+    /// Return an iterator over the nodes after `from_part` that are not in `skip`.
+    ///
+    /// Each visited node is looked up in `skip` by a linear scan, which is
+    /// cheapest for a handful of parts; use [`Robin::exclude_set`] for many.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mywheel_rs::robin::Robin;
+    ///
+    /// let rr = Robin::new(6);
+    /// assert_eq!(rr.exclude_slice(3, &[0, 4]).collect::<Vec<_>>(), vec![5, 1, 2]);
+    /// ```
+    #[inline]
+    pub fn exclude_slice<'a>(
+        &'a self,
+        from_part: I,
+        skip: &'a [I],
+    ) -> impl Iterator<Item = I> + 'a {
+        self.exclude(from_part).filter(move |p| !skip.contains(p))
     }
 
     /// Return an iterator that visits every node **except** `from_part`.
     ///
     /// Iteration begins at the node after `from_part` and stops when it would
-    /// circle back to the excluded node.
+    /// circle back to the excluded node. Only active nodes are visited; if
+    /// `from_part` has been removed, iteration begins at the first active
+    /// node after its old place and visits every active node.
     #[inline]
-    pub fn exclude(&self, from_part: u8) -> RobinIterator<'_> {
+    pub fn exclude(&self, from_part: I) -> RobinIterator<'_, I> {
+        let mut curr = from_part;
+        let mut remaining = self.active_count;
+        if self.is_active(from_part) {
+            remaining -= 1;
+        } else if remaining > 0 {
+            // Follow the stale successors to an active node, then step back
+            let n = self.first_active(&self.next, from_part);
+            curr = self.prev[n.to_usize()];
+        }
         RobinIterator {
             next: &self.next,
-            curr: from_part,
-            stop: from_part,
+            curr,
+            remaining,
         }
     }
 }
 
-impl<'a> Iterator for RobinIterator<'a> {
-    type Item = u8;
+impl<'a, I: PartIndex> Iterator for RobinIterator<'a, I> {
+    type Item = I;
 
     /// Advance to the next node and yield its key.
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        let nxt = self.next[self.curr as usize];
-        if nxt == self.stop {
+        if self.remaining == 0 {
             None
         } else {
-            self.curr = nxt;
+            self.remaining -= 1;
+            self.curr = self.next[self.curr.to_usize()];
             Some(self.curr)
         }
     }
 }
 
+impl<I: PartIndex> Default for RobinCursor<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: PartIndex> RobinCursor<I> {
+    /// Construct a cursor positioned before part 0.
+    #[inline]
+    pub fn new() -> Self {
+        RobinCursor { last: None }
+    }
+
+    /// Return the next active part of `robin` after the cursor, skipping `part`.
+    ///
+    /// If the last part handed out has since been removed from `robin`, the
+    /// cursor continues at the first active part after its old place.
+    /// Returns `None` if no active part other than `part` exists.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mywheel_rs::robin::{Robin, RobinCursor};
+    ///
+    /// let mut rr = Robin::new(5);
+    /// let mut cursor = RobinCursor::new();
+    /// assert_eq!(cursor.next_excluding(&rr, 0), Some(1));
+    /// assert_eq!(cursor.next_excluding(&rr, 0), Some(2));
+    /// rr.remove(2);
+    /// rr.remove(3);
+    /// assert_eq!(cursor.next_excluding(&rr, 0), Some(4));
+    /// ```
+    pub fn next_excluding(&mut self, robin: &Robin<I>, part: I) -> Option<I> {
+        if robin.active_count == 0 {
+            return None;
+        }
+        let mut c = match self.last {
+            None => robin.at_or_after(I::from_usize(0)),
+            Some(last) if robin.is_active(last) => robin.next[last.to_usize()],
+            Some(last) => robin.first_active(&robin.next, last),
+        };
+        if c == part {
+            if robin.active_count == 1 {
+                return None;
+            }
+            c = robin.next[c.to_usize()];
+        }
+        self.last = Some(c);
+        Some(c)
+    }
+
+    /// Move the cursor onto `part` of `robin`, so the next call continues after it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `part` is not a node of `robin`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mywheel_rs::robin::Robin;
+    ///
+    /// let rr = Robin::new(5);
+    /// let mut cursor = rr.cursor();
+    /// cursor.skip(&rr, 2);
+    /// assert_eq!(cursor.next_excluding(&rr, 0), Some(3));
+    /// ```
+    #[inline]
+    pub fn skip(&mut self, robin: &Robin<I>, part: I) {
+        assert!(
+            part.to_usize() < robin.num_parts(),
+            "RobinCursor::skip: part {part:?} is not in the cycle"
+        );
+        self.last = Some(part);
+    }
+
+    /// Move the cursor back before part 0.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+
+    /// Return the last part handed out, if any.
+    #[inline]
+    pub fn position(&self) -> Option<I> {
+        self.last
+    }
+}
+
+/// Round-robin tournament schedule built with the circle method.
+///
+/// Yields rounds of disjoint pairs such that every two parts meet exactly
+/// once over all rounds, for pairwise k-way refinement. With `n` parts there
+/// are `n - 1` rounds of `n / 2` pairs when `n` is even; when `n` is odd one
+/// part sits out (has a bye) in each round and there are `n` rounds. Fewer
+/// than 2 parts give no rounds. The schedule is deterministic, and each
+/// pair lists the part seated first before the other.
+///
+/// ```svgbob
+///   round 0:   p0 ---- p5        round 1:   p0 ---- p4
+///              p1 ---- p4                   p5 ---- p3
+///              p2 ---- p3                   p1 ---- p2
+///   (p0 stays put, the others rotate one seat per round)
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use mywheel_rs::robin::Tournament;
+///
+/// let rounds: Vec<Vec<(u8, u8)>> = Tournament::new(4).collect();
+/// assert_eq!(rounds, vec![
+///     vec![(0, 3), (1, 2)],
+///     vec![(0, 2), (1, 3)],
+///     vec![(0, 1), (2, 3)],
+/// ]);
+/// assert_eq!(Tournament::new(3).next(), Some(vec![(1, 2)])); // 0 has a bye
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tournament<I = u8> {
+    /// Seats in the order given, padded with a bye (`None`) to an even count.
+    seats: Vec<Option<I>>,
+    /// The next round to yield.
+    round: usize,
+}
+
+impl Tournament {
+    /// Construct a tournament over parts `0..num_parts`.
+    #[inline]
+    pub fn new(num_parts: u8) -> Tournament {
+        Tournament::from_parts((0..num_parts).collect())
+    }
+}
+
+impl<I: PartIndex> Tournament<I> {
+    /// Construct a tournament over the given parts, seated in order.
+    pub fn from_parts(parts: Vec<I>) -> Self {
+        if parts.len() < 2 {
+            // Nobody to pair: no seats, no rounds
+            return Tournament {
+                seats: Vec::new(),
+                round: 0,
+            };
+        }
+        let mut seats: Vec<Option<I>> = parts.into_iter().map(Some).collect();
+        if seats.len() % 2 == 1 {
+            seats.push(None);
+        }
+        Tournament { seats, round: 0 }
+    }
+
+    /// Return the total number of rounds.
+    #[inline]
+    pub fn num_rounds(&self) -> usize {
+        self.seats.len().saturating_sub(1)
+    }
+
+    /// Return the index into `seats` of position `pos` in round `round`:
+    /// seat 0 is fixed and the others rotate by one position per round.
+    #[inline]
+    fn seat(&self, round: usize, pos: usize) -> usize {
+        if pos == 0 {
+            0
+        } else {
+            let m = self.seats.len() - 1;
+            1 + (pos - 1 + m - round % m) % m
+        }
+    }
+}
+
+impl<I: PartIndex> Iterator for Tournament<I> {
+    type Item = Vec<(I, I)>;
+
+    /// Yield the pairs of the next round.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.round >= self.num_rounds() {
+            return None;
+        }
+        let n = self.seats.len();
+        let round = self.round;
+        self.round += 1;
+        Some(
+            (0..n / 2)
+                .filter_map(|i| {
+                    let (x, y) = (self.seat(round, i), self.seat(round, n - 1 - i));
+                    match (self.seats[x.min(y)], self.seats[x.max(y)]) {
+                        (Some(a), Some(b)) => Some((a, b)),
+                        _ => None,
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+/// SplitMix64 pseudo-random generator, enough for seeded shuffles.
+#[derive(Debug, Clone)]
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Return a value in `0..bound` (Lemire's multiply-shift reduction).
+    fn below(&mut self, bound: u64) -> u64 {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+}
+
+/// Fixed-size set of parts stored as bit flags.
+///
+/// Used with [`Robin::exclude_set`] to skip several parts, e.g. the source
+/// part and every part that is already full, without allocating per call.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PartSet {
+    /// Bit `i % 64` of `words[i / 64]` is set when part `i` is in the set.
+    words: Vec<u64>,
+}
+
+impl PartSet {
+    /// Construct an empty set for parts `0..num_parts`.
+    pub fn new(num_parts: usize) -> Self {
+        PartSet {
+            words: vec![0; (num_parts + 63) / 64],
+        }
+    }
+
+    /// Add `part` to the set; returns `false` if it was already there.
+    #[inline]
+    pub fn insert(&mut self, part: usize) -> bool {
+        let (w, bit) = (part / 64, 1u64 << (part % 64));
+        let fresh = self.words[w] & bit == 0;
+        self.words[w] |= bit;
+        fresh
+    }
+
+    /// Remove `part` from the set; returns `false` if it was not there.
+    #[inline]
+    pub fn remove(&mut self, part: usize) -> bool {
+        let (w, bit) = (part / 64, 1u64 << (part % 64));
+        let present = self.words[w] & bit != 0;
+        self.words[w] &= !bit;
+        present
+    }
+
+    /// Return whether `part` is in the set; parts beyond the capacity never are.
+    #[inline]
+    pub fn contains(&self, part: usize) -> bool {
+        self.words
+            .get(part / 64)
+            .map_or(false, |w| w & (1u64 << (part % 64)) != 0)
+    }
+
+    /// Return the number of parts in the set.
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Return whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Remove all parts from the set.
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|w| *w = 0);
+    }
+}
+
+/// Smooth weighted round-robin over a fixed set of parts.
+///
+/// Part `i` appears `weights[i]` times per period (after dividing all
+/// weights by their gcd), interleaved as evenly as possible with the other
+/// parts, as in nginx's smooth weighted round-robin: weights `[5, 1, 1]`
+/// give `0 0 1 0 2 0 0` instead of the bursty `0 0 0 0 0 1 2`.
+///
+/// Nothing is precomputed: each pick runs the nginx step (add every weight
+/// to its part's running score, pick the highest score, subtract the total
+/// from it) in O(n) time, so any weights, up to `u32::MAX` each, are fine.
+///
+/// # Example
+///
+/// ```rust
+/// use mywheel_rs::robin::WeightedRobin;
+///
+/// let wrr = WeightedRobin::<u16>::new(&[2, 4, 2]);
+/// assert_eq!(wrr.period().collect::<Vec<_>>(), vec![1, 0, 2, 1]);
+/// assert_eq!(wrr.exclude(1).collect::<Vec<_>>(), vec![2, 0]);
+///
+/// let skewed = WeightedRobin::<u8>::new(&[u32::MAX, 1]);
+/// assert_eq!(skewed.period_len(), u32::MAX as u64 + 1);
+/// assert_eq!(skewed.exclude(0).collect::<Vec<_>>(), vec![1]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WeightedRobin<I = u8> {
+    /// The weights divided by their gcd.
+    weights: Vec<i64>,
+    /// The sum of `weights`.
+    total: i64,
+    marker: std::marker::PhantomData<I>,
+}
+
+/// Iterator over one period of a [`WeightedRobin`], possibly without one part.
+///
+/// Created by [`WeightedRobin::period`] and [`WeightedRobin::exclude`].
+#[derive(Debug)]
+pub struct WeightedRobinIterator<'a, I = u8> {
+    /// The reduced weights of the parent [`WeightedRobin`].
+    weights: &'a [i64],
+    /// Running score of each part.
+    current: Vec<i64>,
+    /// Sum of the weights taking part.
+    total: i64,
+    /// Part scanned first, which wins ties.
+    start: usize,
+    /// The excluded part, or `weights.len()` if none.
+    skip: usize,
+    /// Number of picks left.
+    remaining: u64,
+    marker: std::marker::PhantomData<I>,
+}
+
+impl<I: PartIndex> WeightedRobin<I> {
+    /// Construct a smooth weighted round-robin over `weights.len()` parts.
+    ///
+    /// Parts with weight zero never appear.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are fewer than 2 parts, too many parts for `I`, or
+    /// all weights are zero.
+    pub fn new(weights: &[u32]) -> Self {
+        let n = weights.len();
+        assert!(n >= 2, "WeightedRobin::new: need at least 2 parts, got {n}");
+        assert!(
+            n <= I::MAX_PARTS,
+            "WeightedRobin::new: {n} parts do not fit the index type"
+        );
+        let g = weights.iter().fold(0, |g, &w| gcd(g, w));
+        assert!(g > 0, "WeightedRobin::new: all weights are zero");
+        let weights: Vec<i64> = weights.iter().map(|&w| (w / g) as i64).collect();
+        let total = weights.iter().sum();
+        WeightedRobin {
+            weights,
+            total,
+            marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Return the number of parts.
+    #[inline]
+    pub fn num_parts(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Return the length of one period, the sum of the reduced weights.
+    #[inline]
+    pub fn period_len(&self) -> u64 {
+        self.total as u64
+    }
+
+    /// Return an iterator over one period of the weighted sequence.
+    ///
+    /// Ties go to the lowest part index.
+    #[inline]
+    pub fn period(&self) -> WeightedRobinIterator<'_, I> {
+        self.iter_from(0, self.weights.len())
+    }
+
+    /// Return an iterator over one period of the other parts, skipping `from_part`.
+    ///
+    /// The sequence is the smooth weighted round-robin over the remaining
+    /// parts, so each other part `i` is visited `weights[i]` times (reduced
+    /// by the gcd) and the excluded part costs nothing. Ties go to the parts
+    /// after `from_part` in cyclic order, so equal weights visit the parts
+    /// in the same order as [`Robin::exclude`].
+    #[inline]
+    pub fn exclude(&self, from_part: I) -> WeightedRobinIterator<'_, I> {
+        let skip = from_part.to_usize();
+        self.iter_from((skip + 1) % self.weights.len(), skip)
+    }
+
+    fn iter_from(&self, start: usize, skip: usize) -> WeightedRobinIterator<'_, I> {
+        let total = self.total - self.weights.get(skip).copied().unwrap_or(0);
+        WeightedRobinIterator {
+            weights: &self.weights,
+            current: vec![0; self.weights.len()],
+            total,
+            start,
+            skip,
+            remaining: total as u64,
+            marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, I: PartIndex> Iterator for WeightedRobinIterator<'a, I> {
+    type Item = I;
+
+    /// Run one step of smooth weighted round-robin in O(n).
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let n = self.weights.len();
+        let mut best = self.skip;
+        for k in 0..n {
+            let i = (self.start + k) % n;
+            if i == self.skip {
+                continue;
+            }
+            self.current[i] += self.weights[i];
+            if best == self.skip || self.current[i] > self.current[best] {
+                best = i;
+            }
+        }
+        self.current[best] -= self.total;
+        Some(I::from_usize(best))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = usize::try_from(self.remaining).unwrap_or(usize::MAX);
+        (n, usize::try_from(self.remaining).ok())
+    }
+}
+
+/// Greatest common divisor.
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
 
     #[test]
     fn test_robin_count() {
@@ -178,4 +1076,380 @@ mod tests {
             assert_eq!(sorted, expected);
         }
     }
+
+    #[test]
+    fn test_robin_wide_index() {
+        for n in [2usize, 255, 256, 1024] {
+            let rr16 = Robin::<u16>::with_num_parts(n);
+            let rr32 = Robin::<u32>::with_num_parts(n);
+            let rrsz = Robin::<usize>::with_num_parts(n);
+            let excluded = n / 2;
+            let visited: Vec<usize> = rr16.exclude(excluded as u16).map(usize::from).collect();
+            let expected: Vec<usize> = (1..n).map(|k| (excluded + k) % n).collect();
+            assert_eq!(visited, expected);
+            assert!(rr32
+                .exclude(excluded as u32)
+                .map(|i| i as usize)
+                .eq(expected.iter().copied()));
+            assert!(rrsz.exclude(excluded).eq(expected.iter().copied()));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit")]
+    fn test_robin_too_many_parts() {
+        Robin::<u8>::with_num_parts(257);
+    }
+
+    #[test]
+    fn test_robin_max_parts() {
+        assert_eq!(<u8 as PartIndex>::MAX_PARTS, 256);
+        assert_eq!(<u16 as PartIndex>::MAX_PARTS, 65_536);
+        assert_eq!(<usize as PartIndex>::MAX_PARTS, usize::MAX);
+        let rr = Robin::<u8>::with_num_parts(256);
+        assert_eq!(rr.exclude(255).next(), Some(0));
+        assert_eq!(rr.exclude(0).count(), 255);
+    }
+
+    #[test]
+    fn test_weighted_robin_smooth() {
+        let wrr = Robin::with_weights(&[5, 1, 1]);
+        assert_eq!(wrr.period().collect::<Vec<_>>(), vec![0, 0, 1, 0, 2, 0, 0]);
+        assert_eq!(wrr.exclude(0).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(wrr.exclude(1).collect::<Vec<_>>(), vec![0, 0, 2, 0, 0, 0]);
+        // Equal weights degenerate to the plain cycle
+        let eq = Robin::with_weights(&[3, 3, 3, 3]);
+        let plain = Robin::new(4);
+        for p in 0..4 {
+            assert!(eq.exclude(p).eq(plain.exclude(p)));
+        }
+    }
+
+    #[test]
+    fn test_weighted_robin_zero_weight() {
+        let wrr = WeightedRobin::<u32>::new(&[0, 2, 1]);
+        assert_eq!(wrr.period().collect::<Vec<_>>(), vec![1, 2, 1]);
+        assert_eq!(wrr.exclude(0).collect::<Vec<_>>(), vec![1, 2, 1]);
+        assert_eq!(wrr.exclude(1).collect::<Vec<_>>(), vec![2]);
+        assert_eq!(WeightedRobin::<u8>::new(&[0, 4]).exclude(1).count(), 0);
+    }
+
+    #[test]
+    fn test_weighted_robin_huge_weights() {
+        let wrr = Robin::with_weights(&[u32::MAX, 1, u32::MAX - 1]);
+        assert_eq!(wrr.period_len(), 2 * u32::MAX as u64);
+        let head: Vec<u8> = wrr.period().take(4).collect();
+        assert_eq!(head, vec![0, 2, 0, 2]);
+        assert_eq!(wrr.exclude(0).take(3).collect::<Vec<_>>(), vec![2, 2, 2]);
+    }
+
+    #[test]
+    fn prop_weighted_robin_shares() {
+        fn prop(weights: Vec<u8>, from: u8) -> bool {
+            let weights: Vec<u32> = weights.iter().take(12).map(|&w| (w % 8) as u32).collect();
+            if weights.len() < 2 || weights.iter().all(|&w| w == 0) {
+                return true;
+            }
+            let wrr = Robin::with_weights(&weights);
+            let g = weights.iter().fold(0, |g, &w| gcd(g, w));
+            let from = from % weights.len() as u8;
+            let mut counts = vec![0u32; weights.len()];
+            for p in wrr.exclude(from) {
+                counts[p as usize] += 1;
+            }
+            // Smoothness: every prefix of the period holds each part's share to within 2 slots
+            let period: Vec<u8> = wrr.period().collect();
+            let total = period.len() as i64;
+            let mut seen = vec![0i64; weights.len()];
+            let mut smooth = true;
+            for (k, &p) in period.iter().enumerate() {
+                seen[p as usize] += 1;
+                smooth &= weights.iter().zip(&seen).all(|(&w, &c)| {
+                    ((k as i64 + 1) * (w / g) as i64 - total * c).abs() < 2 * total
+                });
+            }
+            smooth
+                && counts.iter().enumerate().all(|(i, &c)| {
+                    c == if i == from as usize {
+                        0
+                    } else {
+                        weights[i] / g
+                    }
+                })
+        }
+        quickcheck::QuickCheck::new().quickcheck(prop as fn(Vec<u8>, u8) -> bool);
+    }
+
+    #[test]
+    fn test_robin_remove_all_and_insert() {
+        let mut rr = Robin::<u16>::with_num_parts(3);
+        for p in 0..3 {
+            assert!(rr.remove(p));
+        }
+        assert_eq!(rr.active_count(), 0);
+        assert_eq!(rr.exclude(1).count(), 0);
+        assert!(rr.insert(1));
+        assert_eq!(rr.exclude(1).count(), 0);
+        assert_eq!(rr.exclude(0).collect::<Vec<_>>(), vec![1]);
+        assert!(rr.insert(0));
+        assert_eq!(rr.exclude(1).collect::<Vec<_>>(), vec![0]);
+        // Part 2 was the last one out, so it has no old neighbour to return to
+        assert!(rr.insert(2));
+        assert_eq!(rr.exclude(2).collect::<Vec<_>>(), vec![1, 0]);
+    }
+
+    #[test]
+    fn test_robin_insert_fallbacks() {
+        let mut rr = Robin::new(6);
+        // Old predecessor gone: re-linked before the old successor
+        rr.remove(1);
+        rr.remove(2);
+        rr.insert(1);
+        assert_eq!(rr.exclude(0).collect::<Vec<_>>(), vec![1, 3, 4, 5]);
+        // Both old neighbours gone: re-linked before the anchor (part 0)
+        rr.remove(4);
+        rr.remove(3);
+        rr.remove(5);
+        rr.insert(4);
+        assert_eq!(rr.exclude(0).collect::<Vec<_>>(), vec![1, 4]);
+    }
+
+    #[test]
+    fn prop_robin_remove_insert() {
+        fn prop(ops: Vec<(bool, u8)>) -> bool {
+            const N: u8 = 9;
+            let mut rr = Robin::new(N);
+            let mut active = [true; N as usize];
+            for (insert, part) in ops {
+                let part = part % N;
+                let changed = if insert {
+                    rr.insert(part)
+                } else {
+                    rr.remove(part)
+                };
+                if changed != (active[part as usize] != insert) {
+                    return false;
+                }
+                active[part as usize] = insert;
+                for from in 0..N {
+                    let mut visited: Vec<u8> = rr.exclude(from).collect();
+                    visited.sort_unstable();
+                    let expected: Vec<u8> = (0..N)
+                        .filter(|&p| p != from && active[p as usize])
+                        .collect();
+                    if visited != expected {
+                        return false;
+                    }
+                }
+            }
+            true
+        }
+        quickcheck::QuickCheck::new().quickcheck(prop as fn(Vec<(bool, u8)>) -> bool);
+    }
+
+    #[test]
+    fn prop_robin_reinsert_restores_order() {
+        fn prop(parts: Vec<u8>) -> bool {
+            const N: u8 = 9;
+            let mut rr = Robin::new(N);
+            let mut removed = Vec::new();
+            for part in parts {
+                if rr.remove(part % N) {
+                    removed.push(part % N);
+                }
+            }
+            for &part in removed.iter().rev() {
+                rr.insert(part);
+            }
+            (0..N).all(|from| rr.exclude(from).eq((1..N).map(|k| (from + k) % N)))
+        }
+        quickcheck::QuickCheck::new().quickcheck(prop as fn(Vec<u8>) -> bool);
+    }
+
+    #[test]
+    fn test_robin_cursor_inactive_parts() {
+        let mut rr = Robin::new(5);
+        rr.remove(0);
+        rr.remove(3);
+        let mut cursor = rr.cursor();
+        assert_eq!(cursor.next_excluding(&rr, 1), Some(2));
+        assert_eq!(cursor.next_excluding(&rr, 1), Some(4));
+        assert_eq!(cursor.next_excluding(&rr, 1), Some(2));
+        cursor.skip(&rr, 3); // removed: continue at its old successor
+        assert_eq!(cursor.next_excluding(&rr, 2), Some(4));
+        cursor.reset();
+        assert_eq!(cursor.position(), None);
+        assert_eq!(cursor.next_excluding(&rr, 4), Some(1));
+
+        let mut single = Robin::new(2);
+        single.remove(1);
+        let mut cursor = single.cursor();
+        assert_eq!(cursor.next_excluding(&single, 0), None);
+        assert_eq!(cursor.next_excluding(&single, 1), Some(0));
+    }
+
+    #[test]
+    fn test_robin_cursor_across_removal() {
+        // The cursor lives next to its cycle, which changes between rounds
+        struct Arbiter {
+            robin: Robin,
+            cursor: RobinCursor,
+        }
+        let mut arb = Arbiter {
+            robin: Robin::new(6),
+            cursor: RobinCursor::new(),
+        };
+        let round = |arb: &mut Arbiter| -> Vec<u8> {
+            (0..3)
+                .filter_map(|_| arb.cursor.next_excluding(&arb.robin, 0))
+                .collect()
+        };
+        assert_eq!(round(&mut arb), vec![1, 2, 3]);
+        // Remove the part the cursor stopped on, and the one after it
+        arb.robin.remove(3);
+        arb.robin.remove(4);
+        assert_eq!(round(&mut arb), vec![5, 1, 2]);
+        arb.robin.insert(4);
+        assert_eq!(round(&mut arb), vec![4, 5, 1]);
+    }
+
+    #[test]
+    fn prop_robin_cursor_resumes() {
+        fn prop(excluded: Vec<u8>) -> bool {
+            const N: u8 = 7;
+            let rr = Robin::new(N);
+            let mut cursor = rr.cursor();
+            let mut last = N - 1;
+            for &x in &excluded {
+                let x = x % N;
+                // The part after the previous one, stepping over the excluded part
+                let mut expected = (last + 1) % N;
+                if expected == x {
+                    expected = (expected + 1) % N;
+                }
+                if cursor.next_excluding(&rr, x) != Some(expected) {
+                    return false;
+                }
+                last = expected;
+            }
+            true
+        }
+        quickcheck::QuickCheck::new().quickcheck(prop as fn(Vec<u8>) -> bool);
+    }
+
+    #[test]
+    fn test_part_set() {
+        let mut set = PartSet::new(130);
+        assert!(set.insert(0));
+        assert!(set.insert(129));
+        assert!(!set.insert(129));
+        assert!(set.contains(129) && !set.contains(64) && !set.contains(500));
+        assert_eq!(set.len(), 2);
+        assert!(set.remove(0));
+        assert!(!set.remove(0));
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn prop_robin_exclude_set() {
+        fn prop(skip: Vec<u16>, from: u16, removed: Vec<u16>) -> bool {
+            const N: usize = 300;
+            let mut rr = Robin::<u16>::with_num_parts(N);
+            for &p in &removed {
+                rr.remove(p % N as u16);
+            }
+            let mut set = PartSet::new(N);
+            for &p in &skip {
+                set.insert(p as usize % N);
+            }
+            let from = from % N as u16;
+            // Removals keep the natural order, so the oracle walks 0..N cyclically
+            let skipped: HashSet<u16> = skip.iter().map(|&p| p % N as u16).collect();
+            let removed: HashSet<u16> = removed.iter().map(|&p| p % N as u16).collect();
+            let expected: Vec<u16> = (1..=N as u16)
+                .map(|k| (from + k) % N as u16)
+                .filter(|p| *p != from && !removed.contains(p) && !skipped.contains(p))
+                .collect();
+            let slice: Vec<u16> = skipped.iter().copied().collect();
+            rr.exclude_set(from, &set).eq(expected.iter().copied())
+                && rr.exclude_slice(from, &slice).eq(expected.iter().copied())
+                && rr.filter_iter(from, |p| !skipped.contains(&p)).eq(expected)
+        }
+        quickcheck::QuickCheck::new().quickcheck(prop as fn(Vec<u16>, u16, Vec<u16>) -> bool);
+    }
+
+    #[test]
+    fn test_tournament_pairs_everyone_once() {
+        assert_eq!(Tournament::new(0).num_rounds(), 0);
+        assert_eq!(Tournament::new(1).num_rounds(), 0);
+        assert_eq!(Tournament::new(1).next(), None);
+        assert_eq!(Tournament::new(2).num_rounds(), 1);
+        for n in 0..=13u8 {
+            let rounds: Vec<Vec<(u8, u8)>> = Tournament::new(n).collect();
+            assert_eq!(rounds.len(), Tournament::new(n).num_rounds());
+            let mut met = vec![vec![0; n as usize]; n as usize];
+            for round in &rounds {
+                let mut busy = vec![false; n as usize];
+                for &(a, b) in round {
+                    assert!(!busy[a as usize] && !busy[b as usize]);
+                    busy[a as usize] = true;
+                    busy[b as usize] = true;
+                    met[a as usize][b as usize] += 1;
+                    met[b as usize][a as usize] += 1;
+                }
+                assert_eq!(round.len(), n as usize / 2);
+            }
+            for (a, row) in met.iter().enumerate() {
+                for (b, &count) in row.iter().enumerate() {
+                    assert_eq!(count, usize::from(a != b));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_tournament_reproducible() {
+        let rr = Robin::<u32>::with_num_parts(7);
+        let a: Vec<_> = rr.tournament().collect();
+        let b: Vec<_> = rr.tournament().collect();
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 7);
+        assert_eq!(a[0], vec![(1, 6), (2, 5), (3, 4)]);
+    }
+
+    #[test]
+    fn test_robin_shuffled_differs_by_seed() {
+        let orders: Vec<Vec<u8>> = (0..8)
+            .map(|seed| Robin::shuffled(10, seed).exclude(0).collect())
+            .collect();
+        assert!(orders.iter().any(|o| o != &orders[0]));
+        assert!(orders.iter().any(|o| o != &(1..10).collect::<Vec<u8>>()));
+        // Known value of the generator, so the schedule is reproducible across releases
+        assert_eq!(SplitMix64::new(0).next_u64(), 0xE220_A839_7B1D_CDAF);
+    }
+
+    #[test]
+    fn prop_robin_reshuffle_keeps_contract() {
+        fn prop(seed: u64, removed: Vec<u8>, inserted: Vec<u8>) -> bool {
+            const N: u8 = 11;
+            let mut rr = Robin::new(N);
+            for &p in &removed {
+                rr.remove(p % N);
+            }
+            rr.reshuffle(seed);
+            for &p in &inserted {
+                rr.insert(p % N);
+            }
+            (0..N).all(|from| {
+                let mut visited: Vec<u8> = rr.exclude(from).collect();
+                visited.sort_unstable();
+                visited
+                    == (0..N)
+                        .filter(|&p| p != from && rr.is_active(p))
+                        .collect::<Vec<_>>()
+            })
+        }
+        quickcheck::QuickCheck::new().quickcheck(prop as fn(u64, Vec<u8>, Vec<u8>) -> bool);
+    }
 }
@@ -0,0 +1,391 @@
+use crate::bpqueue::BPQueue;
+use crate::dllist::Dllink;
+use crate::robin::Robin;
+
+/// Per-producer dispatch statistics of a [`Scheduler`].
+///
+/// Waits are measured in dispatches: the number of tasks handed out for
+/// other producers while this producer had a task ready.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProducerStats {
+    /// Number of tasks dispatched for this producer
+    pub served: u64,
+    /// Longest wait before a dispatch
+    pub max_wait: u64,
+    /// Sum of all waits before dispatches
+    pub total_wait: u64,
+}
+
+/// The `Scheduler` struct dispatches tasks from several producers fairly.
+///
+/// Each producer owns a [`BPQueue`] of task nodes keyed by priority. A
+/// [`Robin`] cycle arbitrates between producers: the current producer keeps
+/// the turn for as many dispatches as its weight, then the turn passes to
+/// the next producer in cyclic order that has a task ready. Within a
+/// producer, the highest-priority task is dispatched first.
+///
+/// # Performance Characteristics
+///
+/// * **Time Complexity**:
+///   - Submit and cancel: O(1)
+///   - Next task: O(P) worst case to find the next ready producer, where P
+///     is the number of producers, plus the amortized BPQueue pop
+/// * **Space Complexity**: O(P × priority range)
+/// * **Fairness**: a producer with a ready task waits at most for the sum of
+///   the other producers' weights
+///
+/// # Examples
+///
+/// ```rust
+/// use mywheel_rs::dllist::Dllink;
+/// use mywheel_rs::scheduler::Scheduler;
+///
+/// let mut sched = Scheduler::<&str>::new(2, 0, 10);
+/// let mut a = Dllink::new((0, "a-low"));
+/// let mut b = Dllink::new((0, "a-high"));
+/// let mut c = Dllink::new((0, "b"));
+/// sched.submit(0, &mut a, 1);
+/// sched.submit(0, &mut b, 9);
+/// sched.submit(1, &mut c, 5);
+///
+/// let order: Vec<_> = std::iter::from_fn(|| sched.next_task().map(|(p, t)| (p, t.data.1))).collect();
+/// assert_eq!(order, vec![(0, "a-high"), (1, "b"), (0, "a-low")]);
+/// ```
+#[derive(Debug)]
+pub struct Scheduler<T> {
+    queues: Vec<BPQueue<T>>,
+    robin: Robin,
+    weights: Vec<u32>,
+    /// Number of queued tasks per producer
+    pending: Vec<usize>,
+    stats: Vec<ProducerStats>,
+    /// Dispatch count at which each producer started waiting, if it has tasks
+    waiting_since: Vec<Option<u64>>,
+    /// Producer holding the turn and the dispatches left in its turn
+    current: u8,
+    credits: u32,
+    dispatched: u64,
+    len: usize,
+}
+
+impl<T: Default + Clone> Scheduler<T> {
+    /// Construct a new Scheduler with equal shares
+    ///
+    /// Arguments:
+    ///
+    /// * `num_producers`: The number of producers; must be at least 2.
+    /// * `a`: The lowest task priority.
+    /// * `b`: The highest task priority.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_producers` is less than 2.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::scheduler::Scheduler;
+    /// let sched = Scheduler::<u32>::new(3, -5, 5);
+    ///
+    /// assert!(sched.is_empty());
+    /// assert_eq!(sched.num_producers(), 3);
+    /// ```
+    pub fn new(num_producers: u8, a: i32, b: i32) -> Self {
+        Self::with_weights(&vec![1; num_producers as usize], a, b)
+    }
+
+    /// Construct a new Scheduler with weighted shares
+    ///
+    /// While all producers have tasks ready, producer `i` receives
+    /// `weights[i]` consecutive dispatches per round.
+    ///
+    /// # Panics
+    ///
+    /// Panics if
+    ///
+    /// * there are fewer than 2 producers,
+    /// * there are more than 255 producers, or
+    /// * a weight is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::dllist::Dllink;
+    /// use mywheel_rs::scheduler::Scheduler;
+    ///
+    /// let mut sched = Scheduler::<u32>::with_weights(&[2, 1], 0, 0);
+    /// let mut tasks: Vec<_> = (0..6).map(|i| Dllink::new((0, i))).collect();
+    /// for (i, task) in tasks.iter_mut().enumerate() {
+    ///     sched.submit((i % 2) as u8, task, 0);
+    /// }
+    ///
+    /// let producers: Vec<_> = std::iter::from_fn(|| sched.next_task().map(|(p, _)| p)).collect();
+    /// assert_eq!(producers, vec![0, 0, 1, 0, 1, 1]);
+    /// ```
+    pub fn with_weights(weights: &[u32], a: i32, b: i32) -> Self {
+        let n = weights.len();
+        assert!(n >= 2, "need at least 2 producers: {n}");
+        assert!(n <= u8::MAX as usize, "too many producers: {n}");
+        assert!(weights.iter().all(|&w| w > 0), "weights must be positive");
+        Self {
+            queues: (0..n).map(|_| BPQueue::new(a, b)).collect(),
+            robin: Robin::new(n as u8),
+            weights: weights.to_vec(),
+            pending: vec![0; n],
+            stats: vec![ProducerStats::default(); n],
+            waiting_since: vec![None; n],
+            current: (n - 1) as u8,
+            credits: 0,
+            dispatched: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the number of producers.
+    #[inline]
+    pub fn num_producers(&self) -> usize {
+        self.queues.len()
+    }
+
+    /// Returns the total number of queued tasks.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no task is queued.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of queued tasks of `producer`.
+    #[inline]
+    pub fn pending(&self, producer: u8) -> usize {
+        self.pending[producer as usize]
+    }
+
+    /// Returns the dispatch statistics of `producer`.
+    #[inline]
+    pub fn stats(&self, producer: u8) -> &ProducerStats {
+        &self.stats[producer as usize]
+    }
+
+    /// Queue a task node for `producer` with the given priority
+    ///
+    /// Precondition: the node is not queued.
+    pub fn submit(&mut self, producer: u8, task: &mut Dllink<(usize, T)>, priority: i32) {
+        let p = producer as usize;
+        self.queues[p].append(task, priority);
+        if self.pending[p] == 0 {
+            self.waiting_since[p] = Some(self.dispatched);
+        }
+        self.pending[p] += 1;
+        self.len += 1;
+    }
+
+    /// Remove a queued task node of `producer`
+    ///
+    /// Returns `false` if the node is not queued (see
+    /// [`Dllink::is_linked`]): it has already been dispatched or cancelled,
+    /// or it was never submitted.
+    ///
+    /// Precondition: a queued node was submitted by `producer`. Passing
+    /// another producer would charge its counters and corrupt both queues;
+    /// debug builds check this in time linear in the node's bucket.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::dllist::Dllink;
+    /// use mywheel_rs::scheduler::Scheduler;
+    ///
+    /// let mut sched = Scheduler::<u32>::new(2, 0, 3);
+    /// let mut a = Dllink::new((0, 7));
+    /// sched.submit(1, &mut a, 2);
+    ///
+    /// assert!(sched.cancel(1, &mut a));
+    /// assert!(!sched.cancel(1, &mut a));
+    /// assert!(sched.next_task().is_none());
+    ///
+    /// let mut fresh = Dllink::new((0, 8));
+    /// assert!(!sched.cancel(0, &mut fresh));
+    /// ```
+    pub fn cancel(&mut self, producer: u8, task: &mut Dllink<(usize, T)>) -> bool {
+        if !task.is_linked() {
+            return false;
+        }
+        let p = producer as usize;
+        debug_assert!(
+            self.queues[p]
+                .bucket
+                .get(task.data.0)
+                .map_or(false, |b| b.iter().any(|n| std::ptr::eq(n, &*task))),
+            "task is not queued by producer {producer}"
+        );
+        self.queues[p].detach(task);
+        task.lock();
+        self.pending[p] -= 1;
+        if self.pending[p] == 0 {
+            self.waiting_since[p] = None;
+        }
+        self.len -= 1;
+        true
+    }
+
+    /// Dispatch the next task
+    ///
+    /// Returns the producer and the task node, which is locked, or `None`
+    /// if no task is queued.
+    pub fn next_task(&mut self) -> Option<(u8, &mut Dllink<(usize, T)>)> {
+        if self.len == 0 {
+            return None;
+        }
+        if self.credits == 0 || self.pending[self.current as usize] == 0 {
+            let cur = self.current;
+            let pending = &self.pending;
+            // The producer holding the turn comes last
+            self.current = self
+                .robin
+                .exclude(cur)
+                .find(|&q| pending[q as usize] > 0)
+                .unwrap_or(cur);
+            self.credits = self.weights[self.current as usize];
+        }
+        self.credits -= 1;
+
+        let p = self.current as usize;
+        let wait = self.dispatched - self.waiting_since[p].unwrap_or(self.dispatched);
+        let stats = &mut self.stats[p];
+        stats.served += 1;
+        stats.total_wait += wait;
+        stats.max_wait = stats.max_wait.max(wait);
+
+        self.dispatched += 1;
+        self.pending[p] -= 1;
+        self.waiting_since[p] = if self.pending[p] > 0 {
+            Some(self.dispatched)
+        } else {
+            None
+        };
+        self.len -= 1;
+
+        let task = self.queues[p].popleft();
+        // Safety: popleft returns a valid node that was submitted for producer p
+        unsafe {
+            (*task).lock();
+            Some((self.current, &mut *task))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    #[test]
+    fn test_scheduler_skips_empty_producers() {
+        let mut sched = Scheduler::<u8>::new(4, 0, 0);
+        let mut tasks: Vec<_> = (0..4).map(|i| Dllink::new((0, i))).collect();
+        for (task, producer) in tasks.iter_mut().zip([3, 1, 3, 1]) {
+            sched.submit(producer, task, 0);
+        }
+        let order: Vec<_> =
+            std::iter::from_fn(|| sched.next_task().map(|(p, t)| (p, t.data.1))).collect();
+        assert_eq!(order, vec![(1, 1), (3, 0), (1, 3), (3, 2)]);
+        assert_eq!(sched.stats(1).served, 2);
+        assert_eq!(sched.stats(3).max_wait, 1);
+        assert_eq!(sched.stats(0), &ProducerStats::default());
+    }
+
+    #[test]
+    fn test_scheduler_weighted_shares() {
+        let mut sched = Scheduler::<u8>::with_weights(&[3, 1, 2], 0, 0);
+        let mut tasks: Vec<_> = (0..60).map(|i| Dllink::new((0, i))).collect();
+        for (i, task) in tasks.iter_mut().enumerate() {
+            sched.submit((i % 3) as u8, task, 0);
+        }
+        let first: Vec<_> = (0..12).map(|_| sched.next_task().unwrap().0).collect();
+        assert_eq!(first, vec![0, 0, 0, 1, 2, 2, 0, 0, 0, 1, 2, 2]);
+        assert_eq!(sched.stats(1).max_wait, 5);
+    }
+
+    #[test]
+    fn test_scheduler_cancel_unsubmitted() {
+        let mut sched = Scheduler::<u8>::new(2, 0, 0);
+        let mut queued = Dllink::new((0, 1));
+        let mut fresh = Dllink::new((0, 2));
+        sched.submit(0, &mut queued, 0);
+        assert!(!sched.cancel(0, &mut fresh));
+        assert!(!sched.cancel(1, &mut fresh));
+        assert_eq!(sched.len(), 1);
+        assert_eq!(sched.next_task().map(|(p, t)| (p, t.data.1)), Some((0, 1)));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "task is not queued by producer 1")]
+    fn test_scheduler_cancel_wrong_producer() {
+        let mut sched = Scheduler::<u8>::new(2, 0, 3);
+        let mut task = Dllink::new((0, 1));
+        sched.submit(0, &mut task, 2);
+        sched.cancel(1, &mut task);
+    }
+
+    #[test]
+    #[should_panic(expected = "need at least 2 producers: 1")]
+    fn test_scheduler_one_producer() {
+        Scheduler::<u8>::new(1, 0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "need at least 2 producers: 0")]
+    fn test_scheduler_no_producers() {
+        Scheduler::<u8>::with_weights(&[], 0, 0);
+    }
+
+    /// Dispatch one task and check that it is the best task of its producer
+    fn dispatch_ok(sched: &mut Scheduler<usize>, queued: &mut [Vec<(usize, i32)>]) -> bool {
+        let (p, task) = match sched.next_task() {
+            Some(x) => x,
+            None => return queued.iter().all(|q| q.is_empty()),
+        };
+        let id = task.data.1;
+        let q = &mut queued[p as usize];
+        let best = q.iter().map(|&(_, prio)| prio).max();
+        let pos = match q.iter().position(|&(t, _)| t == id) {
+            Some(pos) => pos,
+            None => return false,
+        };
+        best == Some(q.remove(pos).1)
+    }
+
+    #[test]
+    fn prop_scheduler_priority_and_fairness() {
+        fn prop(ops: Vec<(u8, i8)>, weights: (u8, u8, u8)) -> bool {
+            let weights = [weights.0 % 4 + 1, weights.1 % 4 + 1, weights.2 % 4 + 1].map(u32::from);
+            let total: u32 = weights.iter().sum();
+            let mut sched = Scheduler::<usize>::with_weights(&weights, -8, 8);
+            let mut tasks: Vec<_> = (0..ops.len()).map(|i| Dllink::new((0, i))).collect();
+            let mut queued: Vec<Vec<(usize, i32)>> = vec![Vec::new(); 3];
+
+            for (i, &(op, prio)) in ops.iter().enumerate() {
+                if op >= 128 && !dispatch_ok(&mut sched, &mut queued) {
+                    return false;
+                }
+                let p = op % 3;
+                let prio = prio as i32 % 9;
+                sched.submit(p, &mut tasks[i], prio);
+                queued[p as usize].push((i, prio));
+            }
+            while !sched.is_empty() {
+                if !dispatch_ok(&mut sched, &mut queued) {
+                    return false;
+                }
+            }
+            (0..3u8).all(|p| sched.stats(p).max_wait <= (total - weights[p as usize]) as u64)
+        }
+        QuickCheck::new().quickcheck(prop as fn(Vec<(u8, i8)>, (u8, u8, u8)) -> bool);
+    }
+}
@@ -0,0 +1,170 @@
+use crate::bpqueue::{BPQueue, InsertOrder};
+use crate::dllist::Dllink;
+use std::ops::RangeInclusive;
+
+/// Stable argsort of a slice by a bounded integer key
+///
+/// The elements are distributed into the buckets of a [`BPQueue`] keyed by
+/// their distance below the end of `range` and drained from the top, so
+/// equal keys come out in their original order. This is a counting sort in O(n + K) time, where K
+/// is the size of `range`.
+///
+/// Arguments:
+///
+/// * `slice`: The elements to sort.
+/// * `key_fn`: Maps each element to its key.
+/// * `range`: The inclusive range all keys fall in.
+///
+/// Returns:
+///
+/// A permutation `perm` such that `slice[perm[0]], slice[perm[1]], ...` is
+/// sorted by key in ascending order.
+///
+/// # Panics
+///
+/// Panics if a key falls outside `range`, or if `range` is empty or spans
+/// more than `i32::MAX - 1` keys.
+///
+/// # Examples
+///
+/// ```rust
+/// use mywheel_rs::sort::bucket_argsort;
+///
+/// let words = ["ccc", "a", "bb", "d", "ee"];
+/// let perm = bucket_argsort(&words, |w| w.len() as i32, 1..=3);
+///
+/// assert_eq!(perm, vec![1, 3, 2, 4, 0]);
+/// ```
+pub fn bucket_argsort<T, F>(slice: &[T], mut key_fn: F, range: RangeInclusive<i32>) -> Vec<usize>
+where
+    F: FnMut(&T) -> i32,
+{
+    let n = slice.len();
+    let end = i64::from(*range.end());
+    // BPQueue::new(0, span) needs span + 2 buckets to fit in an i32
+    let span = i32::try_from(end - i64::from(*range.start()))
+        .ok()
+        .filter(|&span| span <= i32::MAX - 2)
+        .expect("key range too wide");
+    let mut nodes: Vec<Dllink<(usize, usize)>> = (0..n).map(|i| Dllink::new((0, i))).collect();
+    let mut bpq = BPQueue::<usize>::new(0, span);
+    bpq.extend_from_ids(
+        &mut nodes,
+        slice.iter().enumerate().map(|(i, x)| {
+            let k = key_fn(x);
+            assert!(range.contains(&k), "key {k} out of range");
+            // Lies in 0..=span, so the cast is lossless
+            (i, (end - i64::from(k)) as i32)
+        }),
+        InsertOrder::Stable,
+    );
+    let mut perm = Vec::with_capacity(n);
+    while !bpq.is_empty() {
+        let node = bpq.popleft();
+        // Safety: popleft returns a valid node from `nodes`
+        perm.push(unsafe { (*node).data.1 });
+    }
+    perm
+}
+
+/// Stable in-place bucket sort of a slice by a bounded integer key
+///
+/// Computes [`bucket_argsort`] and then applies the permutation in place
+/// by following its cycles, so the elements are only swapped, never cloned.
+///
+/// Returns:
+///
+/// The applied permutation: the element now at position `i` was at
+/// position `perm[i]` before the sort.
+///
+/// # Panics
+///
+/// Panics if a key falls outside `range`, or if `range` is empty or spans
+/// more than `i32::MAX - 1` keys.
+///
+/// # Examples
+///
+/// ```rust
+/// use mywheel_rs::sort::bucket_sort_by_key;
+///
+/// let mut gains = vec![(2, 'a'), (-1, 'b'), (2, 'c'), (0, 'd')];
+/// let perm = bucket_sort_by_key(&mut gains, |g| g.0, -2..=2);
+///
+/// assert_eq!(gains, vec![(-1, 'b'), (0, 'd'), (2, 'a'), (2, 'c')]);
+/// assert_eq!(perm, vec![1, 3, 0, 2]);
+/// ```
+pub fn bucket_sort_by_key<T, F>(
+    slice: &mut [T],
+    key_fn: F,
+    range: RangeInclusive<i32>,
+) -> Vec<usize>
+where
+    F: FnMut(&T) -> i32,
+{
+    let perm = bucket_argsort(slice, key_fn, range);
+    let mut placed = vec![false; perm.len()];
+    for start in 0..perm.len() {
+        if placed[start] {
+            continue;
+        }
+        // Position j receives the element originally at perm[j]
+        let mut j = start;
+        loop {
+            placed[j] = true;
+            let k = perm[j];
+            if k == start {
+                break;
+            }
+            slice.swap(j, k);
+            j = k;
+        }
+    }
+    perm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+
+    #[test]
+    fn test_bucket_argsort_empty() {
+        let empty: [i32; 0] = [];
+        assert!(bucket_argsort(&empty, |&x| x, 0..=0).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_bucket_argsort_out_of_range() {
+        bucket_argsort(&[1, 5], |&x| x, 0..=3);
+    }
+
+    #[test]
+    fn test_bucket_sort_extreme_keys() {
+        let mut low = [i32::MIN + 2, i32::MIN, i32::MIN + 1, i32::MIN];
+        bucket_sort_by_key(&mut low, |&x| x, i32::MIN..=i32::MIN + 3);
+        assert_eq!(low, [i32::MIN, i32::MIN, i32::MIN + 1, i32::MIN + 2]);
+
+        let mut high = [i32::MAX, i32::MAX - 1];
+        bucket_sort_by_key(&mut high, |&x| x, i32::MAX - 1..=i32::MAX);
+        assert_eq!(high, [i32::MAX - 1, i32::MAX]);
+    }
+
+    #[test]
+    #[should_panic(expected = "key range too wide")]
+    fn test_bucket_argsort_range_too_wide() {
+        bucket_argsort(&[0], |&x| x, i32::MIN..=-2);
+    }
+
+    #[test]
+    fn prop_bucket_sort_matches_std() {
+        fn prop(xs: Vec<(i8, u8)>) -> bool {
+            let mut ours = xs.clone();
+            let perm = bucket_sort_by_key(&mut ours, |x| x.0 as i32, -128..=127);
+            let mut expected = xs.clone();
+            expected.sort_by_key(|x| x.0); // stable
+            ours == expected && perm.iter().map(|&i| xs[i]).eq(ours.iter().copied())
+        }
+        QuickCheck::new().quickcheck(prop as fn(Vec<(i8, u8)>) -> bool);
+    }
+}
@@ -0,0 +1,240 @@
+use crate::bpqueue::{BPQueue, InsertOrder};
+use crate::dllist::Dllink;
+use crate::graph::Csr;
+
+/// Approximate minimum degree ordering of a symmetric sparse matrix.
+///
+/// Repeatedly eliminates a variable of minimum (approximate) degree. The
+/// elimination graph is kept implicitly as a quotient graph: an eliminated
+/// variable becomes an *element* standing for the clique of its remaining
+/// neighbours, and elements adjacent to it are absorbed into the new one,
+/// so memory never grows beyond the original pattern. Degrees live in a
+/// [`BPQueue`] keyed by the negated degree and are updated with
+/// `modify_key`, since they only change by small deltas around each pivot.
+///
+/// The degree of a variable `i` is bounded by
+/// `|A_i| + Σ_{e ∈ E_i} (|L_e| - 1)`, where `A_i` are its variable
+/// neighbours, `E_i` its adjacent elements and `L_e` the variables of
+/// element `e`, capped at the number of remaining variables.
+///
+/// # Performance Characteristics
+///
+/// * **Space Complexity**: O(V + E)
+/// * **Use Cases**: Fill-reducing orderings before sparse Cholesky/LU factorization
+///
+/// Arguments:
+///
+/// * `csr`: The sparsity pattern. It must be symmetric; diagonal entries are ignored.
+///
+/// Returns:
+///
+/// A permutation `perm` where `perm[k]` is the variable eliminated at step `k`.
+///
+/// # Examples
+///
+/// ```rust
+/// use mywheel_rs::graph::Csr;
+/// use mywheel_rs::sparse::min_degree_ordering;
+///
+/// // Arrow pattern: variable 0 is coupled to all others
+/// let edges: Vec<_> = (1..5).flat_map(|v| [(0, v), (v, 0)]).collect();
+/// let perm = min_degree_ordering(&Csr::from_edges(5, &edges));
+///
+/// // The hub waits until it is no worse than the remaining leaves
+/// assert!(!perm[..3].contains(&0));
+/// ```
+pub fn min_degree_ordering(csr: &Csr) -> Vec<usize> {
+    let n = csr.num_nodes();
+    if n == 0 {
+        return vec![];
+    }
+    let mut vars: Vec<Vec<usize>> = (0..n)
+        .map(|i| {
+            let mut adj: Vec<usize> = csr
+                .neighbors(i)
+                .iter()
+                .copied()
+                .filter(|&j| j != i)
+                .collect();
+            adj.sort_unstable();
+            adj.dedup();
+            adj
+        })
+        .collect();
+    let mut elems: Vec<Vec<usize>> = vec![vec![]; n];
+    let mut elem_vars: Vec<Vec<usize>> = vec![vec![]; n];
+    let mut eliminated = vec![false; n];
+    let mut absorbed = vec![false; n];
+    let mut degree: Vec<usize> = vars.iter().map(|adj| adj.len()).collect();
+
+    let mut bpq = BPQueue::<usize>::new(-(n as i32 - 1), 0);
+    let mut nodes: Vec<Dllink<(usize, usize)>> = (0..n).map(|i| Dllink::new((0, i))).collect();
+    bpq.extend_from_ids(
+        &mut nodes,
+        (0..n).map(|i| (i, -(degree[i] as i32))),
+        InsertOrder::Stable,
+    );
+
+    let mut mark = vec![0; n];
+    let mut stamp = 0;
+    let mut perm = Vec::with_capacity(n);
+    while !bpq.is_empty() {
+        // Safety: the queue is not empty, so popleft returns one of `nodes`
+        let p = unsafe { (*bpq.popleft()).data.1 };
+        nodes[p].lock();
+        eliminated[p] = true;
+        perm.push(p);
+
+        // L_p: the remaining variables reachable from p directly or
+        // through its adjacent elements, which p now absorbs.
+        stamp += 1;
+        mark[p] = stamp;
+        let mut lp = vec![];
+        for &j in vars[p].iter() {
+            if !eliminated[j] && mark[j] != stamp {
+                mark[j] = stamp;
+                lp.push(j);
+            }
+        }
+        for &e in elems[p].iter() {
+            absorbed[e] = true;
+            for &j in elem_vars[e].iter() {
+                if !eliminated[j] && mark[j] != stamp {
+                    mark[j] = stamp;
+                    lp.push(j);
+                }
+            }
+            elem_vars[e] = vec![];
+        }
+        vars[p] = vec![];
+        elems[p] = vec![];
+
+        for &i in lp.iter() {
+            elems[i].retain(|&e| !absorbed[e]);
+            elems[i].push(p);
+            // Variables in L_p are now reached through element p.
+            vars[i].retain(|&j| !eliminated[j] && mark[j] != stamp);
+        }
+        elem_vars[p] = lp;
+
+        let remaining = n - perm.len();
+        for &i in elem_vars[p].iter() {
+            let bound = vars[i].len()
+                + elems[i]
+                    .iter()
+                    .map(|&e| elem_vars[e].len() - 1)
+                    .sum::<usize>();
+            let new = bound.min(remaining - 1);
+            bpq.modify_key(&mut nodes[i], degree[i] as i32 - new as i32);
+            degree[i] = new;
+        }
+    }
+    perm
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+    use std::collections::BTreeSet;
+
+    fn symmetric(n: usize, edges: &[(usize, usize)]) -> Csr {
+        let both: Vec<_> = edges.iter().flat_map(|&(u, v)| [(u, v), (v, u)]).collect();
+        Csr::from_edges(n, &both)
+    }
+
+    /// Number of fill edges created by eliminating in the order `perm`
+    fn fill_in(csr: &Csr, perm: &[usize]) -> usize {
+        let n = csr.num_nodes();
+        let mut adj: Vec<BTreeSet<usize>> = (0..n)
+            .map(|i| {
+                csr.neighbors(i)
+                    .iter()
+                    .copied()
+                    .filter(|&j| j != i)
+                    .collect()
+            })
+            .collect();
+        let mut fill = 0;
+        for &p in perm {
+            let nbrs: Vec<usize> = adj[p].iter().copied().collect();
+            for (k, &u) in nbrs.iter().enumerate() {
+                adj[u].remove(&p);
+                for &v in &nbrs[k + 1..] {
+                    if adj[u].insert(v) {
+                        adj[v].insert(u);
+                        fill += 1;
+                    }
+                }
+            }
+        }
+        fill
+    }
+
+    fn is_permutation(perm: &[usize], n: usize) -> bool {
+        let mut seen = vec![false; n];
+        perm.len() == n && perm.iter().all(|&p| !std::mem::replace(&mut seen[p], true))
+    }
+
+    #[test]
+    fn test_min_degree_arrow() {
+        let edges: Vec<_> = (1..8).map(|v| (0, v)).collect();
+        let csr = symmetric(8, &edges);
+        let perm = min_degree_ordering(&csr);
+        assert!(is_permutation(&perm, 8));
+        assert_eq!(fill_in(&csr, &perm), 0);
+        assert_eq!(fill_in(&csr, &(0..8).collect::<Vec<_>>()), 21);
+    }
+
+    #[test]
+    fn test_min_degree_grid() {
+        // 4x4 grid graph
+        let mut edges = vec![];
+        for r in 0..4 {
+            for c in 0..4 {
+                if c + 1 < 4 {
+                    edges.push((4 * r + c, 4 * r + c + 1));
+                }
+                if r + 1 < 4 {
+                    edges.push((4 * r + c, 4 * r + c + 4));
+                }
+            }
+        }
+        let csr = symmetric(16, &edges);
+        let perm = min_degree_ordering(&csr);
+        assert!(is_permutation(&perm, 16));
+        assert!(fill_in(&csr, &perm) < fill_in(&csr, &(0..16).collect::<Vec<_>>()));
+        assert!(min_degree_ordering(&Csr::from_edges(0, &[])).is_empty());
+    }
+
+    #[test]
+    fn prop_min_degree_tree_has_no_fill() {
+        fn prop(parents: Vec<u8>) -> bool {
+            // Vertex v + 1 hangs below some earlier vertex.
+            let edges: Vec<_> = parents
+                .iter()
+                .enumerate()
+                .map(|(v, &p)| (v + 1, p as usize % (v + 1)))
+                .collect();
+            let n = parents.len() + 1;
+            let csr = symmetric(n, &edges);
+            let perm = min_degree_ordering(&csr);
+            is_permutation(&perm, n) && fill_in(&csr, &perm) == 0
+        }
+        QuickCheck::new().quickcheck(prop as fn(Vec<u8>) -> bool);
+    }
+
+    #[test]
+    fn prop_min_degree_is_permutation() {
+        fn prop(edges: Vec<(u8, u8)>) -> bool {
+            let n = 15;
+            let edges: Vec<_> = edges
+                .iter()
+                .map(|&(u, v)| (u as usize % n, v as usize % n))
+                .collect();
+            let csr = symmetric(n, &edges);
+            is_permutation(&min_degree_ordering(&csr), n)
+        }
+        QuickCheck::new().quickcheck(prop as fn(Vec<(u8, u8)>) -> bool);
+    }
+}
@@ -0,0 +1,498 @@
+use crate::dllist::{Dllink, Dllist};
+
+/// Number of bits of the tick consumed by each level
+const SLOT_BITS: u32 = 6;
+/// Number of slots per level
+const NUM_SLOTS: usize = 1 << SLOT_BITS;
+/// Number of levels; timers further out than `NUM_SLOTS^NUM_LEVELS` ticks overflow
+const NUM_LEVELS: usize = 4;
+/// Index of the overflow list in `slots`
+const OVERFLOW: usize = NUM_LEVELS * NUM_SLOTS;
+/// Index of the expired list in `slots`
+const EXPIRED: usize = OVERFLOW + 1;
+/// Index of the scratch list used while cascading
+const SCRATCH: usize = EXPIRED + 1;
+
+#[doc = svgbobdoc::transform!(
+/// The `TimerWheel` struct is a hierarchical timing wheel of intrusive timer nodes.
+///
+/// Each level is an array of 64 [`Dllist`] slots with an implicit rotating
+/// cursor given by the current tick. Level 0 has one slot per tick, level 1
+/// one slot per 64 ticks, and so on. A timer is filed in the lowest level
+/// whose slot still distinguishes its expiry tick from the current tick;
+/// when the cursor of a higher level moves onto a slot, that slot's timers
+/// cascade down. Timers too far out for all levels wait in an overflow list.
+///
+/// The slot rotation deliberately does not use a [`Robin`](crate::robin::Robin)
+/// cycle: the cursor of each level is just the matching 6-bit digit of the
+/// current tick, so it can never drift from `now`, and [`TimerWheel::advance`]
+/// can jump over empty stretches without stepping a separate cursor per level.
+///
+/// The wheel does not own its nodes. A node is a `Dllink<(u64, T)>` whose
+/// first field holds the expiry tick.
+///
+/// # Performance Characteristics
+///
+/// * **Time Complexity**:
+///   - Schedule: O(1)
+///   - Cancel: O(1) via [`Dllink::detach`]
+///   - Advance: O(levels × slots + overflow) per occupied slot reached, plus
+///     O(1) per cascaded or expired timer; empty stretches of ticks are skipped
+/// * **Space Complexity**: O(levels × slots) + O(1) per timer
+/// * **Use Cases**: Event-driven simulators, timeouts, retransmission timers
+/// * **vs std::collections::BinaryHeap**: O(1) insert and cancel instead of O(log n)
+///
+/// ```svgbob
+///   level 1   +---+---+---+---+- - -+---+
+///             |   |{c}|   |   |     |   |   1 slot = 64 ticks
+///             +---+---+---+---+- - -+---+
+///                   |  cascade
+///                   v
+///   level 0   +---+---+---+---+- - -+---+
+///             |   |{c}|{c}|   |     |   |   1 slot = 1 tick
+///             +---+---+---+---+- - -+---+
+///                   ^
+///                   cursor = now mod 64
+///
+/// # Legend:
+/// c = {
+///     fill: papayawhip;
+/// }
+/// ```
+///
+/// # Examples
+///
+/// ```rust
+/// use mywheel_rs::dllist::Dllink;
+/// use mywheel_rs::timer_wheel::TimerWheel;
+///
+/// let mut wheel = TimerWheel::<u32>::new();
+/// let mut a = Dllink::new((0, 1));
+/// let mut b = Dllink::new((0, 2));
+/// wheel.schedule(&mut a, 5);
+/// wheel.schedule(&mut b, 300);
+///
+/// assert_eq!(wheel.advance(10).map(|n| n.data.1).collect::<Vec<_>>(), vec![1]);
+/// assert!(wheel.cancel(&mut b));
+/// assert!(wheel.is_empty());
+/// ```
+)]
+#[derive(Debug)]
+pub struct TimerWheel<T> {
+    now: u64,
+    len: usize,
+    /// Wheel slots level by level, then the overflow, expired and scratch lists
+    slots: Vec<Dllist<(u64, T)>>,
+}
+
+impl<T: Default> TimerWheel<T> {
+    /// Construct a new TimerWheel object starting at tick 0
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::timer_wheel::TimerWheel;
+    /// let wheel = TimerWheel::<u32>::new();
+    ///
+    /// assert_eq!(wheel.now(), 0);
+    /// assert!(wheel.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        let mut slots: Vec<Dllist<(u64, T)>> = (0..SCRATCH + 1)
+            .map(|_| Dllist::new((u64::MAX, T::default())))
+            .collect();
+        for lst in slots.iter_mut() {
+            lst.clear();
+        }
+        Self {
+            now: 0,
+            len: 0,
+            slots,
+        }
+    }
+}
+
+impl<T: Default> Default for TimerWheel<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> TimerWheel<T> {
+    /// Returns the current tick.
+    #[inline]
+    pub fn now(&self) -> u64 {
+        self.now
+    }
+
+    /// Returns the number of scheduled timers, including expired ones not yet yielded.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no timer is scheduled.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Schedule a node to expire at the absolute tick `tick`
+    ///
+    /// A tick that is not in the future expires on the next call to
+    /// [`TimerWheel::advance`].
+    ///
+    /// Precondition: the node is not scheduled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::dllist::Dllink;
+    /// use mywheel_rs::timer_wheel::TimerWheel;
+    ///
+    /// let mut wheel = TimerWheel::<u32>::new();
+    /// let mut a = Dllink::new((0, 1));
+    /// wheel.schedule(&mut a, 100_000_000);
+    ///
+    /// assert_eq!(a.data.0, 100_000_000);
+    /// assert_eq!(wheel.len(), 1);
+    /// ```
+    pub fn schedule(&mut self, node: &mut Dllink<(u64, T)>, tick: u64) {
+        node.data.0 = tick;
+        self.place(node);
+        self.len += 1;
+    }
+
+    /// Cancel a scheduled node in O(1)
+    ///
+    /// Returns `false` if the node is not scheduled (see
+    /// [`Dllink::is_linked`]): it has already expired or been cancelled, or
+    /// it was never scheduled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::dllist::Dllink;
+    /// use mywheel_rs::timer_wheel::TimerWheel;
+    ///
+    /// let mut wheel = TimerWheel::<u32>::new();
+    /// let mut a = Dllink::new((0, 1));
+    /// wheel.schedule(&mut a, 7);
+    ///
+    /// assert!(wheel.cancel(&mut a));
+    /// assert!(!wheel.cancel(&mut a));
+    /// assert_eq!(wheel.advance(10).count(), 0);
+    ///
+    /// let mut fresh = Dllink::new((0, 2));
+    /// assert!(!wheel.cancel(&mut fresh));
+    /// ```
+    pub fn cancel(&mut self, node: &mut Dllink<(u64, T)>) -> bool {
+        if !node.is_linked() {
+            return false;
+        }
+        node.detach();
+        node.lock();
+        self.len -= 1;
+        true
+    }
+
+    /// Move the clock forward by `ticks` and return the timers that expired
+    ///
+    /// Expired nodes are locked as they are yielded. Nodes not consumed
+    /// from the iterator stay pending and are yielded by the next call.
+    ///
+    /// The clock jumps straight to the next tick at which an occupied slot
+    /// expires or cascades, so stretches without timers cost nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mywheel_rs::dllist::Dllink;
+    /// use mywheel_rs::timer_wheel::TimerWheel;
+    ///
+    /// let mut wheel = TimerWheel::<u32>::new();
+    /// let mut a = Dllink::new((0, 1));
+    /// wheel.schedule(&mut a, 70);
+    ///
+    /// assert_eq!(wheel.advance(69).count(), 0);
+    /// assert_eq!(wheel.advance(1).count(), 1);
+    /// assert!(a.is_locked());
+    /// assert_eq!(wheel.advance(1 << 40).count(), 0);
+    /// ```
+    pub fn advance(&mut self, ticks: u64) -> Expired<'_, T> {
+        let target = self.now + ticks;
+        while let Some(due) = self.next_due().filter(|&due| due <= target) {
+            self.now = due - 1;
+            self.tick();
+        }
+        self.now = target;
+        Expired { wheel: self }
+    }
+
+    /// The first tick after `now` at which [`Self::tick`] touches a non-empty slot
+    ///
+    /// Every non-empty slot lies ahead of the cursor of its level, so it is
+    /// reached exactly when the lower digits of the clock roll over to zero.
+    fn next_due(&mut self) -> Option<u64> {
+        // An overflow timer differs from `now` above the top level, so the
+        // block holding the earliest one starts after `now`
+        let span = NUM_LEVELS as u32 * SLOT_BITS;
+        let mut due = self.slots[OVERFLOW]
+            .iter()
+            .map(|node| node.data.0 >> span << span)
+            .min();
+        for level in 0..NUM_LEVELS {
+            let shift = level as u32 * SLOT_BITS;
+            let digit = (self.now >> shift) as usize & (NUM_SLOTS - 1);
+            let base = self.now >> (shift + SLOT_BITS) << (shift + SLOT_BITS);
+            for slot in digit + 1..NUM_SLOTS {
+                if !self.slots[level * NUM_SLOTS + slot].is_empty() {
+                    let t = base | (slot as u64) << shift;
+                    due = Some(due.map_or(t, |d: u64| d.min(t)));
+                    break;
+                }
+            }
+        }
+        due
+    }
+
+    /// File a node into the slot matching its expiry tick
+    fn place(&mut self, node: &mut Dllink<(u64, T)>) {
+        let exp = node.data.0;
+        let idx = if exp <= self.now {
+            EXPIRED
+        } else {
+            // The highest group of bits in which `exp` and `now` differ
+            let level = ((63 - (exp ^ self.now).leading_zeros()) / SLOT_BITS) as usize;
+            if level >= NUM_LEVELS {
+                OVERFLOW
+            } else {
+                let slot = (exp >> (level as u32 * SLOT_BITS)) as usize & (NUM_SLOTS - 1);
+                level * NUM_SLOTS + slot
+            }
+        };
+        self.slots[idx].append(node);
+    }
+
+    /// Re-file every node of a slot against the current tick
+    fn cascade(&mut self, idx: usize) {
+        // Detach the slot first: overflow nodes may be filed right back into it
+        self.move_slot(idx, SCRATCH);
+        while !self.slots[SCRATCH].is_empty() {
+            let node = self.slots[SCRATCH].popleft();
+            // Safety: popleft returns a valid node that was scheduled on this wheel
+            unsafe {
+                self.place(&mut *node);
+            }
+        }
+    }
+
+    /// Advance the clock by a single tick
+    fn tick(&mut self) {
+        self.now += 1;
+        let mask = |level: usize| (1u64 << (level as u32 * SLOT_BITS)) - 1;
+        if self.now & mask(NUM_LEVELS) == 0 {
+            self.cascade(OVERFLOW);
+        }
+        for level in (1..NUM_LEVELS).rev() {
+            if self.now & mask(level) == 0 {
+                let slot = (self.now >> (level as u32 * SLOT_BITS)) as usize & (NUM_SLOTS - 1);
+                self.cascade(level * NUM_SLOTS + slot);
+            }
+        }
+        self.move_slot(self.now as usize & (NUM_SLOTS - 1), EXPIRED);
+    }
+
+    /// Splice all nodes of slot `src` onto the back of slot `dst > src`
+    fn move_slot(&mut self, src: usize, dst: usize) {
+        let (lo, hi) = self.slots.split_at_mut(dst);
+        hi[0].splice(&mut lo[src]);
+    }
+}
+
+/// Iterator over the timers expired by [`TimerWheel::advance`].
+///
+/// Yields the expired nodes and locks each of them. Nodes that were already
+/// due before the call, i.e. scheduled at or before the current tick or left
+/// unconsumed by a previous call, come first in the order they became due;
+/// the rest follow tick by tick. Nodes expiring on the same tick are not
+/// ordered among themselves.
+#[derive(Debug)]
+pub struct Expired<'a, T> {
+    wheel: &'a mut TimerWheel<T>,
+}
+
+impl<'a, T> Iterator for Expired<'a, T> {
+    type Item = &'a mut Dllink<(u64, T)>;
+
+    /// Return a next item
+    fn next(&mut self) -> Option<Self::Item> {
+        let lst = &mut self.wheel.slots[EXPIRED];
+        if lst.is_empty() {
+            return None;
+        }
+        let node = lst.popleft();
+        self.wheel.len -= 1;
+        // Safety: popleft returns a valid node owned by the caller of `schedule`
+        unsafe {
+            (*node).lock();
+            Some(&mut *node)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quickcheck::QuickCheck;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_timer_wheel_levels() {
+        let mut wheel = TimerWheel::<usize>::new();
+        let mut nodes: Vec<_> = (0..4).map(|i| Dllink::new((0, i))).collect();
+        for (node, tick) in nodes.iter_mut().zip([3, 64, 4_100, 70_000]) {
+            wheel.schedule(node, tick);
+        }
+        assert_eq!(
+            wheel.advance(3).map(|n| n.data.1).collect::<Vec<_>>(),
+            vec![0]
+        );
+        assert_eq!(wheel.advance(60).count(), 0);
+        assert_eq!(
+            wheel.advance(1).map(|n| n.data.1).collect::<Vec<_>>(),
+            vec![1]
+        );
+        assert_eq!(wheel.advance(4_035).count(), 0);
+        assert_eq!(
+            wheel.advance(1).map(|n| n.data.1).collect::<Vec<_>>(),
+            vec![2]
+        );
+        assert_eq!(wheel.advance(65_899).count(), 0);
+        assert_eq!(
+            wheel.advance(1).map(|n| n.data.1).collect::<Vec<_>>(),
+            vec![3]
+        );
+        assert!(wheel.is_empty());
+        assert_eq!(wheel.now(), 70_000);
+    }
+
+    #[test]
+    fn test_timer_wheel_past_and_pending() {
+        let mut wheel = TimerWheel::<usize>::default();
+        let mut a = Dllink::new((0, 1));
+        let mut b = Dllink::new((0, 2));
+        wheel.advance(100).count();
+        wheel.schedule(&mut a, 50);
+        wheel.schedule(&mut b, 100);
+        assert_eq!(wheel.advance(0).next().map(|n| n.data.1), Some(1));
+        assert_eq!(wheel.len(), 1);
+        assert!(wheel.cancel(&mut b)); // cancel while pending in the expired list
+        assert_eq!(wheel.advance(0).count(), 0);
+        assert!(wheel.is_empty());
+    }
+
+    #[test]
+    fn test_timer_wheel_overflow() {
+        let mut wheel = TimerWheel::<usize>::new();
+        let mut a = Dllink::new((0, 1));
+        let far = 1u64 << 30;
+        wheel.schedule(&mut a, far);
+        assert_eq!(wheel.advance(far - 1).count(), 0);
+        assert_eq!(wheel.now(), far - 1);
+        assert_eq!(
+            wheel.advance(1).map(|n| n.data.0).collect::<Vec<_>>(),
+            vec![far]
+        );
+    }
+
+    #[test]
+    fn test_timer_wheel_cancel_fresh_node() {
+        let mut wheel = TimerWheel::<usize>::new();
+        let mut a = Dllink::new((0, 1));
+        let mut b = Dllink::new((0, 2));
+        wheel.schedule(&mut b, 3);
+        assert!(!wheel.cancel(&mut a));
+        assert_eq!(wheel.len(), 1);
+        assert_eq!(wheel.advance(3).count(), 1);
+    }
+
+    #[test]
+    fn test_timer_wheel_sparse_advance() {
+        let mut wheel = TimerWheel::<usize>::new();
+        assert_eq!(wheel.advance(1 << 40).count(), 0);
+        assert_eq!(wheel.now(), 1 << 40);
+        let mut nodes: Vec<_> = (0..3).map(|i| Dllink::new((0, i))).collect();
+        let ticks = [(1 << 40) + 5, (1 << 41) + 4_097, 1 << 50];
+        for (node, &tick) in nodes.iter_mut().zip(ticks.iter()) {
+            wheel.schedule(node, tick);
+        }
+        let got: Vec<u64> = wheel.advance(1 << 51).map(|n| n.data.0).collect();
+        assert_eq!(got, ticks);
+        assert!(wheel.is_empty());
+    }
+
+    #[test]
+    fn prop_timer_wheel_matches_btreemap() {
+        fn prop(ops: Vec<(u8, u8, u16)>) -> bool {
+            const NUM_TIMERS: usize = 16;
+            let mut wheel = TimerWheel::<usize>::new();
+            let mut nodes: Vec<Dllink<(u64, usize)>> =
+                (0..NUM_TIMERS).map(|i| Dllink::new((0, i))).collect();
+            for node in nodes.iter_mut() {
+                node.lock();
+            }
+            let mut reference = BTreeMap::new(); // (tick, id) -> ()
+            let mut scheduled: Vec<Option<u64>> = vec![None; NUM_TIMERS];
+
+            for &(op, id, arg) in &ops {
+                let id = id as usize % NUM_TIMERS;
+                match op % 3 {
+                    0 => {
+                        if let Some(tick) = scheduled[id].take() {
+                            reference.remove(&(tick, id));
+                            wheel.cancel(&mut nodes[id]);
+                        }
+                        // Mostly near timers, sometimes a few levels up
+                        let delay = if arg % 8 == 0 {
+                            arg as u64 * 64
+                        } else {
+                            arg as u64 % 300
+                        };
+                        let tick = wheel.now() + delay;
+                        wheel.schedule(&mut nodes[id], tick);
+                        reference.insert((tick, id), ());
+                        scheduled[id] = Some(tick);
+                    }
+                    1 => {
+                        let expected = scheduled[id].take().is_some();
+                        reference.retain(|&(_, i), _| i != id);
+                        if wheel.cancel(&mut nodes[id]) != expected {
+                            return false;
+                        }
+                    }
+                    _ => {
+                        let now = wheel.now() + arg as u64 % 2_000;
+                        let mut got: Vec<(u64, usize)> =
+                            wheel.advance(arg as u64 % 2_000).map(|n| n.data).collect();
+                        got.sort_unstable();
+                        let due: Vec<(u64, usize)> =
+                            reference.range(..(now + 1, 0)).map(|(&k, _)| k).collect();
+                        for &(tick, id) in &due {
+                            reference.remove(&(tick, id));
+                            scheduled[id] = None;
+                        }
+                        if got != due {
+                            return false;
+                        }
+                    }
+                }
+                if wheel.len() != reference.len() {
+                    return false;
+                }
+            }
+            true
+        }
+        QuickCheck::new().quickcheck(prop as fn(Vec<(u8, u8, u16)>) -> bool);
+    }
+}
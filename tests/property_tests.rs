@@ -61,6 +61,20 @@ fn prop_shift_array_length() {
     QuickCheck::new().quickcheck(prop as fn(Vec<i32>) -> bool);
 }
 
+// Property: ShiftArray iter yields the same values as items, for any start
+#[test]
+fn prop_shift_array_iter_matches_items() {
+    fn prop(values: Vec<i32>, start: u16) -> bool {
+        let mut array = ShiftArray::new(values.clone());
+        array.set_start(start as usize);
+        array.iter().eq(array.items().map(|(_, v)| *v))
+            && array
+                .items()
+                .all(|(i, v)| array.get(i) == Some(v) && array[i] == *v)
+    }
+    QuickCheck::new().quickcheck(prop as fn(Vec<i32>, u16) -> bool);
+}
+
 // Property: MapAdapter get returns Some only for valid indices
 #[test]
 fn prop_map_adapter_get_bounds() {